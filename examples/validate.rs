@@ -11,7 +11,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .fin();
 
     for event in events.iter() {
-        dbg!(validator.validate(&event));
+        dbg!(validator.validate(event));
     }
 
     Ok(())
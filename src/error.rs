@@ -19,6 +19,64 @@ impl FieldError {
 
 pub type FieldResult<T> = Result<T, FieldError>;
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PathParseError {
+    #[error("The path is empty")]
+    Empty,
+
+    #[error("Empty alternative at position {0}")]
+    EmptyAlternative(usize),
+
+    #[error("Stray separator at position {0}")]
+    StraySeparator(usize),
+
+    #[error("Unclosed quote starting at position {0}")]
+    UnclosedQuote(usize),
+
+    #[error("Unclosed bracket starting at position {0}")]
+    UnclosedBracket(usize),
+
+    #[error("Unexpected closing bracket at position {0}")]
+    UnexpectedBracket(usize),
+
+    #[error("Nothing to escape at position {0}")]
+    DanglingEscape(usize),
+}
+
+impl PathParseError {
+    /// Moves the reported position by `offset`.
+    pub(crate) fn shifted(self, offset: usize) -> Self {
+        use PathParseError::*;
+        match self {
+            Empty => Empty,
+            EmptyAlternative(i) => EmptyAlternative(i + offset),
+            StraySeparator(i) => StraySeparator(i + offset),
+            UnclosedQuote(i) => UnclosedQuote(i + offset),
+            UnclosedBracket(i) => UnclosedBracket(i + offset),
+            UnexpectedBracket(i) => UnexpectedBracket(i + offset),
+            DanglingEscape(i) => DanglingEscape(i + offset),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("No value bound to placeholder {{{0}}}")]
+    Unbound(String),
+
+    #[error("Unclosed placeholder at position {0}")]
+    Unclosed(usize),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum JsonPathError {
+    #[error("A JSONPath has to start with `$`")]
+    MissingRoot,
+
+    #[error("Unsupported JSONPath syntax at position {0}")]
+    Unsupported(usize),
+}
+
 pub trait Invalidatable {
     fn invalid(&self) -> Option<&str>;
 }
@@ -30,6 +88,14 @@ pub trait FieldResultExt<T> {
     fn if_missing_try<F: FnOnce() -> FieldResult<T>>(self, f: F) -> FieldResult<T>;
     fn is_invalid(&self) -> bool;
     fn is_missing(&self) -> bool;
+
+    /// Turns `Missing` into `Ok(None)`, while `Invalid` stays an error.
+    fn maybe(self) -> FieldResult<Option<T>>;
+
+    /// Falls back to `T::default()` only if the original is Missing.
+    fn or_default(self) -> FieldResult<T>
+    where
+        T: Default;
 }
 
 impl<T> Invalidatable for FieldResult<T> {
@@ -52,14 +118,25 @@ impl<T> FieldResultExt<T> for FieldResult<T> {
     }
 
     fn is_missing(&self) -> bool {
-        if let Err(FieldError::Missing) = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Err(FieldError::Missing))
     }
 
     fn is_invalid(&self) -> bool {
         self.invalid().is_some()
     }
+
+    fn maybe(self) -> FieldResult<Option<T>> {
+        match self {
+            Ok(value) => Ok(Some(value)),
+            Err(FieldError::Missing) => Ok(None),
+            Err(invalid) => Err(invalid),
+        }
+    }
+
+    fn or_default(self) -> FieldResult<T>
+    where
+        T: Default,
+    {
+        self.if_missing_try(|| Ok(T::default()))
+    }
 }
@@ -0,0 +1,188 @@
+//! Compatibility with JSONPath expressions.
+//!
+//! Translates expressions like `$.store.book[0].title` into ordinary paths
+//! anchored at the document root,
+//! so expressions from other tooling can be reused with `PathFinder`.
+//!
+//! Supported are child access via `.name`, `['name']` and `[0]`, unions like `[0,2]`, wildcards (`*`),
+//! recursive descent (`..`) and simple filters like `[?(@.price < 10)]`.
+
+use crate::{error::JsonPathError, path::Paths};
+
+/// Parses a JSONPath expression into `Paths`.
+pub fn parse(expr: &str) -> Result<Paths, JsonPathError> {
+    let mut rest = expr.strip_prefix('$').ok_or(JsonPathError::MissingRoot)?;
+    let mut elements = Vec::new();
+
+    while !rest.is_empty() {
+        let position = expr.len() - rest.len();
+        if let Some(after) = rest.strip_prefix("..") {
+            elements.push(String::from("**"));
+            rest = after;
+            if !rest.starts_with('[') {
+                let (name, after) = take_name(rest);
+                elements.push(name_element(name).ok_or(JsonPathError::Unsupported(position))?);
+                rest = after;
+            }
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let (name, after) = take_name(after);
+            elements.push(name_element(name).ok_or(JsonPathError::Unsupported(position))?);
+            rest = after;
+        } else if rest.starts_with('[') {
+            let close = find_closing(rest).ok_or(JsonPathError::Unsupported(position))?;
+            elements.push(
+                bracket_element(&rest[1..close]).ok_or(JsonPathError::Unsupported(position))?,
+            );
+            rest = &rest[close + 1..];
+        } else {
+            return Err(JsonPathError::Unsupported(position));
+        }
+    }
+
+    Ok(Paths(format!("/{}", elements.join("."))))
+}
+
+/// Splits off a dot-notation name, up to the next `.` or `[`.
+fn take_name(s: &str) -> (&str, &str) {
+    let end = s.find(['.', '[']).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+fn name_element(name: &str) -> Option<String> {
+    match name {
+        "" => None,
+        "*" => Some(String::from("*")),
+        _ => Some(quote(name)),
+    }
+}
+
+/// Translates the content of `[...]`.
+fn bracket_element(inner: &str) -> Option<String> {
+    let inner = inner.trim();
+    if inner == "*" {
+        Some(String::from("*"))
+    } else if let Some(union) = union_element(inner) {
+        Some(union)
+    } else if let Some(name) = string_literal(inner) {
+        Some(quote(name))
+    } else if inner.parse::<isize>().is_ok() {
+        Some(inner.to_owned())
+    } else {
+        let predicate = inner.strip_prefix("?(")?.strip_suffix(')')?.trim();
+        filter_element(predicate)
+    }
+}
+
+/// Translates `0, 2` or `'a', 'b'` into `[0,2]` or `[a,b]`.
+fn union_element(inner: &str) -> Option<String> {
+    if !inner.contains(',') {
+        return None;
+    }
+    let keys = inner
+        .split(',')
+        .map(|key| {
+            let key = key.trim();
+            match string_literal(key) {
+                Some(name) => Some(quote(name)),
+                None => key.parse::<isize>().ok().map(|_| key.to_owned()),
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(format!("[{}]", keys.join(",")))
+}
+
+/// Translates `@.price < 10` into `[price<10]`.
+fn filter_element(predicate: &str) -> Option<String> {
+    let predicate = predicate.strip_prefix("@.")?;
+    let op = predicate.find(['=', '!', '<', '>'])?;
+    let (path, rest) = predicate.split_at(op);
+    let (comparison, value) = ["==", "!=", "<=", ">=", "<", ">", "="]
+        .iter()
+        .find_map(|o| rest.strip_prefix(o).map(|value| (*o, value)))?;
+    let comparison = if comparison == "==" { "=" } else { comparison };
+    let value = value.trim();
+    let value = string_literal(value)
+        .map(quote)
+        .unwrap_or_else(|| value.to_owned());
+    Some(format!("[{}{}{}]", path.trim(), comparison, value))
+}
+
+/// Content of a `'single'` or `"double"` quoted string.
+fn string_literal(s: &str) -> Option<&str> {
+    s.strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .or_else(|| s.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+}
+
+/// Position of the `]` closing the bracket `s` starts with.
+fn find_closing(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, ']') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Quotes a key if it contains anything our own path syntax would interpret.
+fn quote(key: &str) -> String {
+    let special = |c| "./|[],\"\\*".contains(c);
+    if key.is_empty() || key.contains(special) {
+        format!("\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        key.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(expr: &str) -> Result<String, JsonPathError> {
+        parse(expr).map(|paths| paths.0)
+    }
+
+    #[test]
+    fn translates_jsonpath() {
+        assert_eq!(
+            translate("$.store.book[0].title"),
+            Ok("/store.book.0.title".into())
+        );
+        assert_eq!(
+            translate("$['store']['book'][-1]"),
+            Ok("/store.book.-1".into())
+        );
+        assert_eq!(translate("$.store.*.price"), Ok("/store.*.price".into()));
+        assert_eq!(translate("$..author"), Ok("/**.author".into()));
+        assert_eq!(translate("$..[0]"), Ok("/**.0".into()));
+        assert_eq!(translate("$.rows[0, -1]"), Ok("/rows.[0,-1]".into()));
+        assert_eq!(translate("$['a','b.c']"), Ok(r#"/[a,"b.c"]"#.into()));
+        assert_eq!(translate("$['a,b']"), Ok(r#"/"a,b""#.into()));
+        assert_eq!(
+            translate("$['server.com'].port"),
+            Ok(r#"/"server.com".port"#.into())
+        );
+        assert_eq!(
+            translate("$.book[?(@.price < 10)].title"),
+            Ok("/book.[price<10].title".into())
+        );
+        assert_eq!(
+            translate("$.book[?(@.author == 'Tolkien')]"),
+            Ok("/book.[author=Tolkien]".into())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_jsonpath() {
+        assert_eq!(translate("store.book"), Err(JsonPathError::MissingRoot));
+        assert_eq!(
+            translate("$.store.book[0:2]"),
+            Err(JsonPathError::Unsupported(12))
+        );
+        assert_eq!(translate("$.store."), Err(JsonPathError::Unsupported(7)));
+    }
+}
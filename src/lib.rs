@@ -9,12 +9,19 @@ use std::{
 };
 
 pub mod error;
+pub mod jsonpath;
 pub mod path;
 mod pathfinder;
+mod scope;
 mod util;
 pub mod validator;
 
-pub use pathfinder::PathFinder;
+pub use pathfinder::{
+    BoolStyle, DateFormat, IndexStyle, KeyMatching, PathFinder, Visitor, YamlType,
+};
+#[cfg(feature = "date_parsing")]
+pub use pathfinder::{EpochUnit, PartialDate};
+pub use scope::{Matching, Scope};
 
 /// conviniently just opens and parses a `.yml` file.
 pub fn open_yaml<P: AsRef<OsStr> + Sized>(path: P) -> Result<Yaml, Box<dyn std::error::Error>> {
@@ -24,8 +31,8 @@ pub fn open_yaml<P: AsRef<OsStr> + Sized>(path: P) -> Result<Yaml, Box<dyn std::
 
 /// Ruby like API to yaml-rust.
 pub fn parse_yaml(file_content: &str) -> Result<yaml_rust::Yaml, Box<dyn std::error::Error>> {
-    Ok(YamlLoader::load_from_str(&file_content)?
-        .get(0)
+    Ok(YamlLoader::load_from_str(file_content)?
+        .first()
         .map(ToOwned::to_owned)
         .unwrap_or_else(|| yaml_rust::Yaml::from_str("[]")))
 }
@@ -1,26 +1,161 @@
+use std::{borrow::Cow, cmp::Ordering, fmt, ops::Div, str::FromStr, sync::OnceLock};
+
+use crate::error::{PathParseError, TemplateError};
+
+/// Owned alternative paths, see `YPaths`.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Paths(pub String);
 
-pub struct YPaths<'a>(pub &'a str);
+/// Alternative paths, the first one that matches wins.
+///
+/// Written as one string like `offer.date|offer_date`
+/// or given as a list like `&["offer.date", "offer_date"]`.
+#[derive(Clone, Copy)]
+pub struct YPaths<'a> {
+    alternatives: Alternatives<'a>,
+    resolution: Option<Resolution>,
+}
+
+/// Which alternatives of a `YPaths` count when more than one resolves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    /// The first one that resolves, what `get()` does unless told otherwise.
+    First,
+    /// The last one that resolves, for layered configurations.
+    Last,
+    /// Every one that resolves, what `get_all()` does unless told otherwise.
+    All,
+}
+
+#[derive(Clone, Copy)]
+enum Alternatives<'a> {
+    Single(YPath<'a>),
+    Joined(&'a str),
+    Listed(&'a [&'a str]),
+    Parsed(&'a [Vec<Segment<'a>>]),
+}
 
 impl Paths {
     pub fn as_ref<'a>(&'a self) -> YPaths<'a> {
-        YPaths(self.0.as_ref())
+        YPaths::from(self.0.as_str())
     }
 }
 
-impl YPaths<'_> {
-    fn str(&self) -> &str {
-        self.0
+impl<'a> YPaths<'a> {
+    /// Same as `YPaths::from()`, but usable in `const`.
+    pub const fn new(paths: &'a str) -> Self {
+        YPaths::of(Alternatives::Joined(paths))
+    }
+
+    const fn of(alternatives: Alternatives<'a>) -> Self {
+        YPaths {
+            alternatives,
+            resolution: None,
+        }
+    }
+
+    /// Overrides which alternatives count, `a|b` with `Resolution::Last` prefers `b`.
+    pub const fn resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// The strategy set by `resolution()`, if any.
+    pub(crate) fn resolution_or(&self, default: Resolution) -> Resolution {
+        self.resolution.unwrap_or(default)
+    }
+
+    /// Checks the syntax of every alternative.
+    ///
+    /// Paths are otherwise taken as they are, typos only show up as missing fields.
+    pub fn parse(paths: &'a str) -> Result<Self, PathParseError> {
+        for path in YPaths::from(paths).alternatives() {
+            let offset = offset_in(paths, path.0);
+            if path.0.is_empty() {
+                return Err(PathParseError::EmptyAlternative(offset));
+            }
+            check_path(path.0).map_err(|e| e.shifted(offset))?;
+        }
+        Ok(YPaths::from(paths))
     }
 
-    pub fn alternatives<'a>(&'a self) -> impl Iterator<Item = YPath<'a>> {
-        self.str().split('|').map(YPath)
+    /// Splits at `|`, ignoring whitespace around it.
+    ///
+    /// Listed alternatives are taken as they are,
+    /// `TypedPath`s and `CompiledPaths` have no text to split and yield nothing.
+    pub fn alternatives(&self) -> impl Iterator<Item = YPath<'a>> {
+        let alternatives: Vec<YPath<'a>> = match self.alternatives {
+            Alternatives::Single(path) => vec![path],
+            Alternatives::Parsed(_) => Vec::new(),
+            Alternatives::Joined(paths) => split_unquoted(paths, &['|'])
+                .map(|path| YPath(path.trim()))
+                .collect(),
+            Alternatives::Listed(paths) => paths.iter().map(|path| YPath(path)).collect(),
+        };
+        alternatives.into_iter()
+    }
+}
+
+impl<'a> YPaths<'a> {
+    /// Falls back to `other` if none of these alternatives resolves.
+    pub fn or(&self, other: impl Into<YPaths<'a>>) -> CompiledPaths<'a> {
+        self.compile().or(other)
+    }
+
+    /// Resolves every alternative below `base`, anchored ones stay as they are.
+    pub fn under(&self, base: impl Into<YPath<'a>>) -> CompiledPaths<'a> {
+        self.compile().under(base)
+    }
+
+    /// Parses every alternative once, for paths that are resolved over and over.
+    pub fn compile(&self) -> CompiledPaths<'a> {
+        CompiledPaths(self.segment_lists().into_owned())
+    }
+
+    /// The alternative at `index` as written, parsed ones in canonical form.
+    pub(crate) fn origin(&self, index: usize) -> Option<YPathBuf> {
+        match self.alternatives {
+            Alternatives::Parsed(lists) => lists
+                .get(index)
+                .map(|segments| YPathBuf(TypedPath(segments.clone()).to_string())),
+            _ => self.alternatives().nth(index).map(YPathBuf::from),
+        }
+    }
+
+    /// Segments of every alternative, in order.
+    pub(crate) fn segment_lists(&self) -> Cow<'a, [Vec<Segment<'a>>]> {
+        match self.alternatives {
+            Alternatives::Parsed(lists) => Cow::Borrowed(lists),
+            _ => Cow::Owned(
+                self.alternatives()
+                    .map(|path| path.segments().collect())
+                    .collect(),
+            ),
+        }
     }
 }
 
 impl<'a> From<&'a str> for YPaths<'a> {
     fn from(s: &'a str) -> YPaths<'a> {
-        YPaths(s)
+        YPaths::of(Alternatives::Joined(s))
+    }
+}
+
+impl<'a> From<YPath<'a>> for YPaths<'a> {
+    fn from(path: YPath<'a>) -> YPaths<'a> {
+        YPaths::of(Alternatives::Single(path))
+    }
+}
+
+impl<'a> From<&'a [&'a str]> for YPaths<'a> {
+    fn from(paths: &'a [&'a str]) -> YPaths<'a> {
+        YPaths::of(Alternatives::Listed(paths))
+    }
+}
+
+impl<'a, const N: usize> From<&'a [&'a str; N]> for YPaths<'a> {
+    fn from(paths: &'a [&'a str; N]) -> YPaths<'a> {
+        YPaths::of(Alternatives::Listed(paths))
     }
 }
 
@@ -30,28 +165,1053 @@ impl From<&str> for Paths {
     }
 }
 
-#[derive(Clone, Copy)]
+/// Compared and hashed by its text, `a.b` and `a/b` are different keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct YPath<'a>(pub &'a str);
 
-impl YPath<'_> {
-    fn str(&self) -> &str {
+impl<'a> YPath<'a> {
+    /// Checks the syntax of the path.
+    ///
+    /// Paths are otherwise taken as they are, typos only show up as missing fields.
+    pub fn parse(path: &'a str) -> Result<Self, PathParseError> {
+        check_path(path)?;
+        Ok(YPath(path))
+    }
+
+    /// Like `parse()`, but panics on invalid syntax.
+    ///
+    /// In `const` context that is a compile error, which is what `ypath!` relies on.
+    pub const fn checked(path: &'a str) -> Self {
+        match check_path(path) {
+            Ok(()) => YPath(path),
+            Err(PathParseError::Empty) => panic!("empty path"),
+            Err(PathParseError::EmptyAlternative(_)) => panic!("empty alternative in path"),
+            Err(PathParseError::StraySeparator(_)) => panic!("stray separator in path"),
+            Err(PathParseError::UnclosedQuote(_)) => panic!("unclosed quote in path"),
+            Err(PathParseError::UnclosedBracket(_)) => panic!("unclosed bracket in path"),
+            Err(PathParseError::UnexpectedBracket(_)) => panic!("unexpected bracket in path"),
+            Err(PathParseError::DanglingEscape(_)) => panic!("dangling escape in path"),
+        }
+    }
+
+    fn str(&self) -> &'a str {
         self.0
     }
 
-    pub fn elements(&self) -> impl Iterator<Item = &str> {
-        self.str()
-            .split(|p| p == '/' || p == '.')
+    /// Splits the path at `/` and `.`.
+    ///
+    /// Elements in double quotes are taken literally, so `hosts."server.com".port`
+    /// addresses the key `server.com`.
+    /// Single characters can be escaped with a backslash, `a\.b.c` addresses `c` in `a.b`.
+    pub fn elements(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        split_unquoted(self.str(), &['/', '.'])
             .filter(|k| !k.is_empty())
+            .map(|element| unescape(unquote(element)))
+    }
+
+    /// The path without its last element, `None` if there is nothing left to drop.
+    pub fn parent(&self) -> Option<YPath<'a>> {
+        let path = self.str();
+        let anchor = anchor_len(path);
+        if anchor == path.len() {
+            return None;
+        }
+        let part = split_unquoted(&path[anchor..], &['/']).last()?;
+        let last = if part == ".." {
+            part
+        } else {
+            split_unquoted(part, &['.']).last()?
+        };
+        let start = offset_in(path, last);
+        Some(YPath(&path[..start.saturating_sub(1).max(anchor)]))
+    }
+
+    /// Drops empty elements, folds `..` into the element before it
+    /// and writes the rest in canonical form, so `a//b/../"c"` becomes `a.c`.
+    ///
+    /// A `..` that follows `**` or nothing at all is kept.
+    pub fn normalize(&self) -> YPathBuf {
+        YPathBuf(TypedPath(self.normalized_segments()).to_string())
+    }
+
+    fn normalized_segments(&self) -> Vec<Segment<'a>> {
+        let mut normalized: Vec<Segment<'a>> = Vec::new();
+        for segment in self.segments() {
+            match normalized.last() {
+                Some(Segment::Root) | Some(Segment::Parent) | Some(Segment::Recursive) | None => {
+                    normalized.push(segment)
+                }
+                Some(_) if segment == Segment::Parent => {
+                    normalized.pop();
+                }
+                Some(_) => normalized.push(segment),
+            }
+        }
+        normalized
+    }
+
+    /// Whether `prefix` is this path or one of its ancestors.
+    ///
+    /// Both are compared normalized, so `a/b.c` starts with `a."b"`.
+    pub fn starts_with(&self, prefix: impl Into<YPath<'a>>) -> bool {
+        self.normalized_segments()
+            .starts_with(&prefix.into().normalized_segments())
+    }
+
+    /// What remains of the path below `prefix`, `None` if it doesn't start with it.
+    pub fn strip_prefix(&self, prefix: impl Into<YPath<'a>>) -> Option<YPathBuf> {
+        let segments = self.normalized_segments();
+        let prefix = prefix.into().normalized_segments();
+        if !segments.starts_with(&prefix) {
+            return None;
+        }
+        Some(YPathBuf(
+            TypedPath(segments[prefix.len()..].to_vec()).to_string(),
+        ))
+    }
+
+    /// The longest path both this and `other` start with.
+    pub fn common_prefix(&self, other: impl Into<YPath<'a>>) -> YPathBuf {
+        let segments = self.normalized_segments();
+        let common = segments
+            .iter()
+            .zip(other.into().normalized_segments())
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        YPathBuf(TypedPath(segments[..common].to_vec()).to_string())
+    }
+
+    /// Interprets the path relative to `base`, anchored paths ignore it.
+    ///
+    /// A reference `../db` stored at `services.web` resolves to `services.db`.
+    pub fn resolve(&self, base: YPath<'_>) -> YPathBuf {
+        if base.0.is_empty() || anchor_len(self.0) > 0 {
+            return self.normalize();
+        }
+        YPath(&format!("{}/{}", base.0, self.0)).normalize()
+    }
+
+    /// A new owned path with `element` appended, see `YPathBuf::push()`.
+    pub fn join(&self, element: impl fmt::Display) -> YPathBuf {
+        let mut path = YPathBuf::from(*self);
+        path.push(element);
+        path
+    }
+
+    /// Parses the path into its segments.
+    ///
+    /// Unlike `elements()` this knows about `*`, `**`, `..`, `[]`, `[key=value]` filters,
+    /// unions like `[0,2,5]` and key patterns like `build-*`.
+    /// A leading `/`, `$.` or `$` anchors the path at the document root.
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'a>> {
+        let path = self.str();
+        let (anchor, path) = match path {
+            "$" => (Some(Segment::Root), ""),
+            _ => match path
+                .strip_prefix('/')
+                .or_else(|| path.strip_prefix("$."))
+                .or_else(|| path.strip_prefix("$/"))
+            {
+                Some(relative) => (Some(Segment::Root), relative),
+                None => (None, path),
+            },
+        };
+        anchor
+            .into_iter()
+            .chain(split_unquoted(path, &['/']).flat_map(|part| {
+                if part == ".." {
+                    vec![Segment::Parent]
+                } else {
+                    split_unquoted(part, &['.'])
+                        .filter(|k| !k.is_empty())
+                        .flat_map(parse_element)
+                        .collect()
+                }
+            }))
+    }
+}
+
+/// An owned path that can be built up element by element, like `PathBuf`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct YPathBuf(String);
+
+impl YPathBuf {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn as_path(&self) -> YPath<'_> {
+        YPath(&self.0)
+    }
+
+    /// Appends one element, escaping anything that would otherwise be read as syntax.
+    ///
+    /// Indices can be pushed as numbers, `path.push(0)`.
+    pub fn push(&mut self, element: impl fmt::Display) {
+        if matches!(self.as_path().segments().last(), Some(Segment::Parent)) {
+            // `...` would read as a key
+            self.0.push('/');
+        } else if self.0.len() > anchor_len(&self.0) || self.0 == "$" {
+            self.0.push('.');
+        }
+        self.0.push_str(&escape(&element.to_string()));
+    }
+
+    /// Drops the last element, returns `false` if there was none.
+    pub fn pop(&mut self) -> bool {
+        match self.as_path().parent() {
+            Some(parent) => {
+                let len = parent.0.len();
+                self.0.truncate(len);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn parent(&self) -> Option<YPath<'_>> {
+        self.as_path().parent()
+    }
+
+    pub fn join(&self, element: impl fmt::Display) -> YPathBuf {
+        self.as_path().join(element)
+    }
+}
+
+/// `base / "clients" / 23 / "name"`, same as `push()`ing each element.
+impl<T: fmt::Display> Div<T> for YPathBuf {
+    type Output = YPathBuf;
+
+    fn div(mut self, element: T) -> YPathBuf {
+        self.push(element);
+        self
+    }
+}
+
+impl<T: fmt::Display> Div<T> for &YPathBuf {
+    type Output = YPathBuf;
+
+    fn div(self, element: T) -> YPathBuf {
+        self.join(element)
+    }
+}
+
+impl<T: fmt::Display> Div<T> for YPath<'_> {
+    type Output = YPathBuf;
+
+    fn div(self, element: T) -> YPathBuf {
+        self.join(element)
+    }
+}
+
+impl From<&str> for YPathBuf {
+    fn from(path: &str) -> YPathBuf {
+        YPathBuf(path.into())
+    }
+}
+
+impl From<YPath<'_>> for YPathBuf {
+    fn from(path: YPath<'_>) -> YPathBuf {
+        YPathBuf(path.0.into())
     }
 }
 
-impl<'a> AsRef<str> for YPaths<'a> {
+impl<'a> From<&'a YPathBuf> for YPaths<'a> {
+    fn from(path: &'a YPathBuf) -> YPaths<'a> {
+        YPaths::from(path.as_path())
+    }
+}
+
+impl AsRef<str> for YPathBuf {
     fn as_ref(&self) -> &str {
-        self.0
+        &self.0
+    }
+}
+
+/// A path built from typed segments.
+///
+/// Nothing is parsed, so keys may contain any character
+/// and `index()` never mistakes a numeric hash key for an array index.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TypedPath<'a>(Vec<Segment<'a>>);
+
+impl<'a> TypedPath<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key(&mut self, key: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.segment(Segment::Key(key.into()))
+    }
+
+    pub fn index(&mut self, index: isize) -> &mut Self {
+        self.segment(Segment::Index(index))
+    }
+
+    pub fn segment(&mut self, segment: Segment<'a>) -> &mut Self {
+        self.0.push(segment);
+        self
+    }
+
+    pub fn segments(&self) -> &[Segment<'a>] {
+        &self.0
+    }
+}
+
+impl<'a> From<&'a TypedPath<'a>> for YPaths<'a> {
+    fn from(path: &'a TypedPath<'a>) -> YPaths<'a> {
+        YPaths::of(Alternatives::Parsed(std::slice::from_ref(&path.0)))
+    }
+}
+
+/// Alternatives parsed into segments up front, see `YPaths::compile()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompiledPaths<'a>(Vec<Vec<Segment<'a>>>);
+
+impl<'a> CompiledPaths<'a> {
+    pub fn or(mut self, other: impl Into<YPaths<'a>>) -> Self {
+        self.0.extend(other.into().segment_lists().iter().cloned());
+        self
+    }
+
+    pub fn under(mut self, base: impl Into<YPath<'a>>) -> Self {
+        let base: Vec<Segment<'a>> = base.into().segments().collect();
+        for segments in &mut self.0 {
+            if segments.first() != Some(&Segment::Root) {
+                segments.splice(0..0, base.iter().cloned());
+            }
+        }
+        self
+    }
+}
+
+impl<'a> From<&'a CompiledPaths<'a>> for YPaths<'a> {
+    fn from(paths: &'a CompiledPaths<'a>) -> YPaths<'a> {
+        YPaths::of(Alternatives::Parsed(&paths.0))
+    }
+}
+
+/// Paths for `static` items, parsed on first use and never again.
+///
+/// `static OFFER_DATE: LazyPaths = LazyPaths::new("offer.date|offer_date");`
+pub struct LazyPaths {
+    paths: &'static str,
+    compiled: OnceLock<CompiledPaths<'static>>,
+}
+
+impl LazyPaths {
+    pub const fn new(paths: &'static str) -> Self {
+        LazyPaths {
+            paths,
+            compiled: OnceLock::new(),
+        }
+    }
+
+    pub fn compiled(&self) -> &CompiledPaths<'static> {
+        self.compiled
+            .get_or_init(|| YPaths::new(self.paths).compile())
+    }
+}
+
+impl<'a> From<&'a LazyPaths> for YPaths<'a> {
+    fn from(paths: &'a LazyPaths) -> YPaths<'a> {
+        YPaths::from(paths.compiled())
+    }
+}
+
+/// A `YPath` whose syntax is checked at compile time.
+///
+/// `ypath!("users.0.name")` expands to a constant,
+/// a typo like `ypath!("users..name")` fails to compile.
+#[macro_export]
+macro_rules! ypath {
+    ($path:expr) => {{
+        const PATH: $crate::path::YPath<'static> = $crate::path::YPath::checked($path);
+        PATH
+    }};
+}
+
+/// Configurable separators for documents whose keys contain `/`, `.` or `|`.
+#[derive(Clone, Debug)]
+pub struct Separators {
+    elements: Vec<char>,
+    alternatives: Vec<char>,
+}
+
+impl Default for Separators {
+    fn default() -> Self {
+        Separators {
+            elements: vec!['/', '.'],
+            alternatives: vec!['|'],
+        }
+    }
+}
+
+impl Separators {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Characters separating the elements of a path, `/` and `.` by default.
+    pub fn elements(&mut self, separators: &[char]) -> &mut Self {
+        self.elements = separators.to_vec();
+        self
+    }
+
+    /// Characters separating alternatives, `|` by default.
+    pub fn alternatives(&mut self, separators: &[char]) -> &mut Self {
+        self.alternatives = separators.to_vec();
+        self
+    }
+
+    /// Translates `path`, written with these separators, into ordinary `Paths`.
+    ///
+    /// Default separators that are not configured are escaped.
+    pub fn paths(&self, path: &str) -> Paths {
+        let mut translated = String::with_capacity(path.len());
+        let mut quoted = false;
+        let mut escaped = false;
+        for c in path.chars() {
+            if escaped {
+                escaped = false;
+                translated.push(c);
+            } else if c == '\\' {
+                escaped = true;
+                translated.push(c);
+            } else if c == '"' {
+                quoted = !quoted;
+                translated.push(c);
+            } else if quoted {
+                translated.push(c);
+            } else if self.alternatives.contains(&c) {
+                translated.push('|');
+            } else if self.elements.contains(&c) {
+                translated.push('/');
+            } else {
+                if "/.|".contains(c) {
+                    translated.push('\\');
+                }
+                translated.push(c);
+            }
+        }
+        Paths(translated)
+    }
+}
+
+/// A path with placeholders like `users.{id}.name`.
+///
+/// `bind()` fills in the placeholders, escaping the values
+/// so they always end up as a single path element.
+#[derive(Clone, Copy)]
+pub struct YPathTemplate<'a>(pub &'a str);
+
+impl YPathTemplate<'_> {
+    /// Names of all placeholders, in order of appearance.
+    pub fn placeholders(&self) -> Result<Vec<&str>, TemplateError> {
+        self.parts().map(|parts| {
+            parts
+                .into_iter()
+                .filter_map(|part| match part {
+                    TemplatePart::Placeholder(name) => Some(name),
+                    TemplatePart::Literal(_) => None,
+                })
+                .collect()
+        })
+    }
+
+    /// Replaces every placeholder with its value.
+    pub fn bind(&self, values: &[(&str, &dyn fmt::Display)]) -> Result<Paths, TemplateError> {
+        let mut path = String::with_capacity(self.0.len());
+        for part in self.parts()? {
+            match part {
+                TemplatePart::Literal(literal) => path.push_str(literal),
+                TemplatePart::Placeholder(name) => {
+                    let (_, value) = values
+                        .iter()
+                        .find(|(key, _)| *key == name)
+                        .ok_or_else(|| TemplateError::Unbound(name.to_owned()))?;
+                    path.push_str(&escape(&value.to_string()));
+                }
+            }
+        }
+        Ok(Paths(path))
+    }
+
+    fn parts(&self) -> Result<Vec<TemplatePart<'_>>, TemplateError> {
+        let mut parts = Vec::new();
+        let mut rest = self.0;
+        while let Some(open) = rest.find('{') {
+            let close = rest[open..]
+                .find('}')
+                .ok_or_else(|| TemplateError::Unclosed(self.0.len() - rest.len() + open))?;
+            parts.push(TemplatePart::Literal(&rest[..open]));
+            parts.push(TemplatePart::Placeholder(&rest[open + 1..open + close]));
+            rest = &rest[open + close + 1..];
+        }
+        parts.push(TemplatePart::Literal(rest));
+        Ok(parts)
+    }
+}
+
+enum TemplatePart<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+impl<'a> From<&'a str> for YPathTemplate<'a> {
+    fn from(s: &'a str) -> YPathTemplate<'a> {
+        YPathTemplate(s)
+    }
+}
+
+/// Finds unbalanced quotes and brackets, dangling escapes and empty elements.
+const fn check_path(path: &str) -> Result<(), PathParseError> {
+    let bytes = path.as_bytes();
+    if bytes.is_empty() {
+        return Err(PathParseError::Empty);
+    }
+
+    // works on bytes so it can run in `const`, all special characters are ASCII
+    let mut quote = None;
+    let mut escape = None;
+    let mut depth = 0;
+    let mut outermost = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if escape.is_some() {
+            escape = None;
+        } else if c == b'\\' {
+            escape = Some(i);
+        } else if c == b'"' {
+            quote = if quote.is_some() { None } else { Some(i) };
+        } else if quote.is_some() {
+        } else if c == b'[' {
+            if depth == 0 {
+                outermost = i;
+            }
+            depth += 1;
+        } else if c == b']' {
+            if depth == 0 {
+                return Err(PathParseError::UnexpectedBracket(i));
+            }
+            depth -= 1;
+        }
+        i += 1;
+    }
+    if let Some(i) = escape {
+        return Err(PathParseError::DanglingEscape(i));
+    }
+    if let Some(i) = quote {
+        return Err(PathParseError::UnclosedQuote(i));
+    }
+    if depth > 0 {
+        return Err(PathParseError::UnclosedBracket(outermost));
+    }
+
+    let mut part = match bytes {
+        [b'$', b'/', ..] | [b'$', b'.', ..] => 2,
+        [b'$', ..] | [b'/', ..] => 1,
+        _ => 0,
+    };
+    if part == bytes.len() {
+        return Ok(());
+    }
+    let mut element = part;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut depth = 0;
+    let mut i = part;
+    while i <= bytes.len() {
+        if i == part && is_parent(bytes, part) {
+            i = part + 3;
+            part = i;
+            element = i;
+            continue;
+        }
+        let c = if i < bytes.len() { bytes[i] } else { b'/' };
+        if escaped {
+            escaped = false;
+        } else if c == b'\\' {
+            escaped = true;
+        } else if c == b'"' {
+            quoted = !quoted;
+        } else if quoted {
+        } else if c == b'[' {
+            depth += 1;
+        } else if c == b']' && depth > 0 {
+            depth -= 1;
+        } else if depth == 0 && (c == b'/' || c == b'.') {
+            if element == i {
+                // point at the separator that has nothing behind it
+                return Err(PathParseError::StraySeparator(if i == bytes.len() {
+                    i - 1
+                } else {
+                    i
+                }));
+            }
+            if c == b'/' {
+                part = i + 1;
+            }
+            element = i + 1;
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Whether the part starting at `start` is `..`.
+const fn is_parent(bytes: &[u8], start: usize) -> bool {
+    start + 2 <= bytes.len()
+        && bytes[start] == b'.'
+        && bytes[start + 1] == b'.'
+        && (start + 2 == bytes.len() || bytes[start + 2] == b'/')
+}
+
+/// Length of a leading `/`, `$.`, `$/` or `$` anchor.
+fn anchor_len(path: &str) -> usize {
+    if path == "$" || path.starts_with('/') {
+        1
+    } else if path.starts_with("$.") || path.starts_with("$/") {
+        2
+    } else {
+        0
+    }
+}
+
+/// Byte position of `inner`, which has to be a slice of `outer`.
+fn offset_in(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+/// Escapes an element, so that it reads back as exactly one key.
+///
+/// Besides every special character, a leading `$` would anchor the path
+/// and an empty element would vanish, so those become `\$` and `""`.
+pub(crate) fn escape(element: &str) -> Cow<'_, str> {
+    if element.is_empty() {
+        return Cow::Borrowed("\"\"");
+    }
+    match escape_chars(element) {
+        escaped if escaped.starts_with('$') => Cow::Owned(format!("\\{}", escaped)),
+        escaped => escaped,
+    }
+}
+
+/// Escapes every character that has a meaning in paths.
+fn escape_chars(element: &str) -> Cow<'_, str> {
+    let special = |c| "\\./|[]\"*,".contains(c);
+    if !element.contains(special) {
+        return Cow::Borrowed(element);
+    }
+    let mut escaped = String::with_capacity(element.len() + 4);
+    for c in element.chars() {
+        if special(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    Cow::Owned(escaped)
+}
+
+/// One step of a path.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Segment<'a> {
+    /// A hash key, or an index if applied to an array.
+    ///
+    /// Text paths can't tell keys from indices, so numeric keys still index arrays.
+    Key(Cow<'a, str>),
+    /// An array index, negative ones count from the end.
+    ///
+    /// Only ever matches array elements, never hash keys.
+    Index(isize),
+    /// Leading `/` or `$`, starts over at the document root.
+    Root,
+    /// `..` between slashes, steps back up to the parent.
+    Parent,
+    /// `*` or `[]`, matches every child.
+    Wildcard,
+    /// `[0,2,5]`, matches each of several keys or indices.
+    Union(Vec<Cow<'a, str>>),
+    /// `build-*`, matches every hash key that fits the pattern.
+    Pattern(Cow<'a, str>),
+    /// `**`, matches at any depth.
+    Recursive,
+    /// `[key=value]`, matches every child that has `value` at `key`.
+    ///
+    /// Other comparisons like `[total>1000]` work as well.
+    Filter(Filter<'a>),
+}
+
+/// Predicate of a `[key=value]` segment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter<'a> {
+    /// path relative to the child
+    pub(crate) path: &'a str,
+    pub(crate) comparison: Comparison,
+    pub(crate) value: Cow<'a, str>,
+}
+
+/// Operator of a filter: `=`, `!=`, `<`, `<=`, `>` or `>=`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl<'a> Filter<'a> {
+    /// Path of the compared value, relative to the child.
+    pub fn path(&self) -> YPath<'a> {
+        YPath(self.path)
+    }
+
+    pub fn comparison(&self) -> Comparison {
+        self.comparison
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Comparison {
+    /// Whether `found <op> value` holds, given how `found` compares to `value`.
+    pub(crate) fn accepts(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Eq => ordering == Ordering::Equal,
+            Comparison::Ne => ordering != Ordering::Equal,
+            Comparison::Lt => ordering == Ordering::Less,
+            Comparison::Le => ordering != Ordering::Greater,
+            Comparison::Gt => ordering == Ordering::Greater,
+            Comparison::Ge => ordering != Ordering::Less,
+        }
+    }
+}
+
+fn parse_element(element: &str) -> Vec<Segment<'_>> {
+    match element {
+        "*" => vec![Segment::Wildcard],
+        "**" => vec![Segment::Recursive],
+        _ if split_bracket(element).is_none() && find_unquoted(element, &['*']).is_some() => {
+            vec![Segment::Pattern(unescape(unquote(element)))]
+        }
+        _ => match split_bracket(element)
+            .and_then(|(key, inner)| parse_bracket(inner).map(|segment| (key, segment)))
+        {
+            Some(("", segment)) => vec![segment],
+            Some((key, segment)) => vec![Segment::Key(unescape(unquote(key))), segment],
+            None => vec![Segment::Key(unescape(unquote(element)))],
+        },
+    }
+}
+
+/// Matches `key` against a pattern in which `*` stands for any number of characters.
+pub(crate) fn matches_pattern(pattern: &str, key: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let mut rest = match key.strip_prefix(parts.next().unwrap_or_default()) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts: Vec<&str> = parts.collect();
+    match parts.split_last() {
+        None => rest.is_empty(),
+        Some((last, middle)) => {
+            for part in middle {
+                match rest.find(part) {
+                    Some(i) => rest = &rest[i + part.len()..],
+                    None => return false,
+                }
+            }
+            rest.ends_with(last)
+        }
+    }
+}
+
+/// Splits `users[name=bob]` into `users` and `name=bob`.
+fn split_bracket(element: &str) -> Option<(&str, &str)> {
+    let open = find_unquoted(element, &['['])?;
+    if !element.ends_with(']') {
+        return None;
+    }
+    Some((&element[..open], &element[open + 1..element.len() - 1]))
+}
+
+/// Interprets the content of `[...]`.
+///
+/// An empty one projects every element like `*`,
+/// a comma separated list like `[0,2,5]` selects several children at once.
+fn parse_bracket(inner: &str) -> Option<Segment<'_>> {
+    if inner.is_empty() {
+        return Some(Segment::Wildcard);
+    }
+    if let Some(filter) = parse_filter(inner) {
+        return Some(Segment::Filter(filter));
+    }
+    let mut keys: Vec<_> = split_unquoted(inner, &[','])
+        .map(|key| unescape(unquote(key.trim())))
+        .collect();
+    if keys.len() == 1 {
+        keys.pop().map(Segment::Key)
+    } else {
+        Some(Segment::Union(keys))
+    }
+}
+
+/// Parses `name=bob` or `total>1000`.
+fn parse_filter(predicate: &str) -> Option<Filter<'_>> {
+    let op = find_unquoted(predicate, &['=', '!', '<', '>'])?;
+    let (comparison, len) = match &predicate[op..] {
+        o if o.starts_with("!=") => (Comparison::Ne, 2),
+        o if o.starts_with("<=") => (Comparison::Le, 2),
+        o if o.starts_with(">=") => (Comparison::Ge, 2),
+        o if o.starts_with('<') => (Comparison::Lt, 1),
+        o if o.starts_with('>') => (Comparison::Gt, 1),
+        o if o.starts_with('=') => (Comparison::Eq, 1),
+        _ => return None,
+    };
+    Some(Filter {
+        path: &predicate[..op],
+        comparison,
+        value: unescape(unquote(&predicate[op + len..])),
+    })
+}
+
+/// Finds the first of `targets` that is neither escaped nor enclosed in double quotes.
+fn find_unquoted(s: &str, targets: &[char]) -> Option<usize> {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            quoted = !quoted;
+        } else if !quoted && targets.contains(&c) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Splits `s` at every separator that is neither escaped nor enclosed in double quotes or brackets.
+fn split_unquoted<'a>(s: &'a str, separators: &[char]) -> impl Iterator<Item = &'a str> {
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            quoted = !quoted;
+        } else if quoted {
+            continue;
+        } else if c == '[' {
+            depth += 1;
+        } else if c == ']' && depth > 0 {
+            depth -= 1;
+        } else if depth == 0 && separators.contains(&c) {
+            parts.push(&s[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&s[start..]);
+    parts.into_iter()
+}
+
+/// Removes escaping backslashes.
+fn unescape(element: &str) -> Cow<'_, str> {
+    if !element.contains('\\') {
+        return Cow::Borrowed(element);
+    }
+    let mut unescaped = String::with_capacity(element.len());
+    let mut chars = element.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            unescaped.extend(chars.next());
+        } else {
+            unescaped.push(c);
+        }
+    }
+    Cow::Owned(unescaped)
+}
+
+/// Strips surrounding double quotes.
+fn unquote(element: &str) -> &str {
+    if element.len() >= 2 && element.starts_with('"') && element.ends_with('"') {
+        &element[1..element.len() - 1]
+    } else {
+        element
+    }
+}
+
+impl<'a> From<&'a str> for YPath<'a> {
+    fn from(path: &'a str) -> YPath<'a> {
+        YPath(path)
     }
 }
+
 impl<'a> AsRef<str> for YPath<'a> {
     fn as_ref(&self) -> &str {
         self.0
     }
 }
+
+/// Canonical form: a leading `/` for anchored paths, elements separated by `.`
+/// and `/` only around `..`.
+impl fmt::Display for YPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.str();
+        let anchor = anchor_len(path);
+        if anchor > 0 {
+            f.write_str("/")?;
+        }
+        let mut previous: Option<&str> = None;
+        for part in split_unquoted(&path[anchor..], &['/']) {
+            let elements: Vec<&str> = if part == ".." {
+                vec![part]
+            } else {
+                split_unquoted(part, &['.'])
+                    .filter(|element| !element.is_empty())
+                    .collect()
+            };
+            for element in elements {
+                match previous {
+                    Some("..") => f.write_str("/")?,
+                    Some(_) if element == ".." => f.write_str("/")?,
+                    Some(_) => f.write_str(".")?,
+                    None => {}
+                }
+                f.write_str(element)?;
+                previous = Some(element);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for YPaths<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Alternatives::Parsed(lists) = self.alternatives {
+            for (i, segments) in lists.iter().enumerate() {
+                if i > 0 {
+                    f.write_str("|")?;
+                }
+                fmt_segments(segments, f)?;
+            }
+            return Ok(());
+        }
+        for (i, path) in self.alternatives().enumerate() {
+            if i > 0 {
+                f.write_str("|")?;
+            }
+            write!(f, "{}", path)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for YPaths<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Alternatives::Parsed(lists) = self.alternatives {
+            return f.debug_tuple("YPaths").field(&lists).finish();
+        }
+        let alternatives: Vec<&str> = self.alternatives().map(|path| path.0).collect();
+        f.debug_tuple("YPaths").field(&alternatives).finish()
+    }
+}
+
+impl fmt::Display for YPathBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_path().fmt(f)
+    }
+}
+
+impl fmt::Display for Paths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+/// `YPath` borrows its input, parse into the owned `YPathBuf` instead.
+impl FromStr for YPathBuf {
+    type Err = PathParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        YPath::parse(s).map(YPathBuf::from)
+    }
+}
+
+/// `YPaths` borrows its input, parse into the owned `Paths` instead.
+impl FromStr for Paths {
+    type Err = PathParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        YPaths::parse(s).map(|_| Paths::from(s))
+    }
+}
+
+/// Written as an equivalent text path.
+impl fmt::Display for TypedPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_segments(&self.0, f)
+    }
+}
+
+fn fmt_segments(segments: &[Segment<'_>], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut previous: Option<&Segment> = None;
+    for segment in segments {
+        match (previous, segment) {
+            (None, _) | (Some(Segment::Root), _) => {}
+            (Some(Segment::Parent), _) | (_, Segment::Parent) => f.write_str("/")?,
+            _ => f.write_str(".")?,
+        }
+        match segment {
+            Segment::Key(key) => f.write_str(&escape(key))?,
+            Segment::Index(index) => write!(f, "{}", index)?,
+            Segment::Root => f.write_str("/")?,
+            Segment::Parent => f.write_str("..")?,
+            Segment::Wildcard => f.write_str("*")?,
+            Segment::Union(keys) => {
+                let keys: Vec<Cow<str>> = keys.iter().map(|key| escape(key)).collect();
+                write!(f, "[{}]", keys.join(","))?
+            }
+            Segment::Pattern(pattern) => {
+                let parts: Vec<Cow<str>> = pattern.split('*').map(escape_chars).collect();
+                f.write_str(&parts.join("*"))?
+            }
+            Segment::Recursive => f.write_str("**")?,
+            Segment::Filter(filter) => write!(
+                f,
+                "[{}{}{}]",
+                filter.path,
+                filter.comparison,
+                escape_chars(&filter.value)
+            )?,
+        }
+        previous = Some(segment);
+    }
+    Ok(())
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Comparison::Eq => "=",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        })
+    }
+}
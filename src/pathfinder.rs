@@ -1,13 +1,232 @@
 #[cfg(feature = "date_parsing")]
 use chrono::prelude::*;
 
+use std::{
+    any::type_name,
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{BTreeMap, HashSet},
+    convert::TryFrom,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    ops::RangeBounds,
+    str::FromStr,
+    time::Duration,
+};
+
 use yaml_rust::{yaml::Array as YamlArray, yaml::Hash as YamlHash, Yaml};
 
+use crate::util::{decode_base64, decode_hex, is_email, parse_color, parse_duration};
 #[cfg(feature = "date_parsing")]
-use crate::util::parse_dmy_date;
+use crate::util::{parse_datetime, parse_dmy_date, parse_dmy_naive_date, parse_timestamp};
 
+use crate::error::FieldResultExt;
 pub use crate::error::{FieldError, FieldResult};
 pub use crate::path::*;
+use crate::scope::{Matching, Scope};
+
+/// How path elements are compared to hash keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyMatching {
+    /// `server.port` only finds `server.port`
+    #[default]
+    Exact,
+    /// `server.port` also finds `Server.Port`
+    CaseInsensitive,
+    /// `max_retries` also finds `maxRetries`, `max-retries` or `MAX_RETRIES`
+    Normalized,
+}
+
+impl KeyMatching {
+    fn matches(self, element: &str, key: &str) -> bool {
+        self.fold(element) == self.fold(key)
+    }
+
+    /// Brings a key or pattern into the form that is compared.
+    fn fold(self, key: &str) -> Cow<'_, str> {
+        match self {
+            KeyMatching::Exact => Cow::Borrowed(key),
+            KeyMatching::CaseInsensitive => Cow::Owned(key.to_lowercase()),
+            KeyMatching::Normalized => Cow::Owned(normalize_key(key)),
+        }
+    }
+}
+
+/// Which strings `get_bool_as()` reads as booleans, besides native YAML booleans.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoolStyle {
+    /// only `true` and `false`, as in `YAML1.2`
+    Strict,
+    /// also `yes/no`, `on/off` and `true/false` strings, case-insensitively
+    #[default]
+    Words,
+    /// also `y/n`, like the full `YAML1.1` truthiness
+    Yaml11,
+    /// only the given words, case-insensitively, e.g. `ja` and `nein`
+    Custom {
+        truthy: &'static [&'static str],
+        falsy: &'static [&'static str],
+    },
+}
+
+impl BoolStyle {
+    fn interpret(self, word: &str) -> Option<bool> {
+        let word = word.to_lowercase();
+        let (truthy, falsy): (&[&str], &[&str]) = match self {
+            BoolStyle::Strict => return None,
+            BoolStyle::Words => (&["yes", "on", "true"], &["no", "off", "false"]),
+            BoolStyle::Yaml11 => (&["y", "yes", "on", "true"], &["n", "no", "off", "false"]),
+            BoolStyle::Custom { truthy, falsy } => (truthy, falsy),
+        };
+        let listed = |words: &[&str]| words.iter().any(|w| w.to_lowercase() == word);
+        if listed(truthy) {
+            Some(true)
+        } else if listed(falsy) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Shape of the node at a path, as returned by `type_of()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YamlType {
+    Str,
+    Int,
+    Float,
+    Bool,
+    Hash,
+    Array,
+    /// an explicit `~`
+    Null,
+    Missing,
+}
+
+/// Callbacks for `PathFinder::walk()`, both do nothing unless overridden.
+pub trait Visitor<'a> {
+    /// Called before the children of `node`, returning `false` skips them.
+    fn enter(&mut self, _path: YPath<'_>, _node: &'a Yaml) -> bool {
+        true
+    }
+
+    /// Called after the children of `node`, even if they were skipped.
+    fn leave(&mut self, _path: YPath<'_>, _node: &'a Yaml) {}
+}
+
+/// How `flatten()` writes array indices.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IndexStyle {
+    /// `server.ports.0`, a valid path
+    #[default]
+    Dotted,
+    /// `server.ports[0]`, like property files
+    Bracketed,
+}
+
+/// Date layouts that `get_date()` can try.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateFormat {
+    /// `25.12.2016`, also with `/` or `-`
+    Dmy,
+    /// `2016/12/25`, also with `.` or `-`
+    Ymd,
+    /// `12/25/2016`, also with `.` or `-`
+    Mdy,
+    /// `2016-12-25`, also a full RFC 3339 or YAML timestamp
+    Iso,
+}
+
+#[cfg(feature = "date_parsing")]
+impl DateFormat {
+    fn parse(self, date: &str) -> Option<NaiveDate> {
+        let date = date.trim();
+        let layouts: &[&str] = match self {
+            DateFormat::Dmy => &["%d.%m.%Y", "%d/%m/%Y", "%d-%m-%Y"],
+            DateFormat::Ymd => &["%Y/%m/%d", "%Y.%m.%d", "%Y-%m-%d"],
+            DateFormat::Mdy => &["%m/%d/%Y", "%m.%d.%Y", "%m-%d-%Y"],
+            DateFormat::Iso => {
+                return parse_datetime(date).map(|datetime| datetime.naive_local().date())
+            }
+        };
+        layouts
+            .iter()
+            .find_map(|layout| NaiveDate::parse_from_str(date, layout).ok())
+    }
+}
+
+/// How `get_timestamp_in()` reads an epoch.
+#[cfg(feature = "date_parsing")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EpochUnit {
+    /// seconds up to eleven digits, milliseconds beyond,
+    /// which misreads milliseconds before March 1973 as seconds
+    #[default]
+    Auto,
+    /// `1573130700`
+    Seconds,
+    /// `1573130700000`
+    Millis,
+}
+
+#[cfg(feature = "date_parsing")]
+impl EpochUnit {
+    fn parse(self, epoch: i64) -> Option<DateTime<Utc>> {
+        match self {
+            EpochUnit::Auto => parse_timestamp(epoch),
+            EpochUnit::Seconds => Utc.timestamp_opt(epoch, 0).single(),
+            EpochUnit::Millis => Utc.timestamp_millis_opt(epoch).single(),
+        }
+    }
+}
+
+/// A date that may only be known to the month or year, as returned by `get_partial_date()`.
+#[cfg(feature = "date_parsing")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartialDate {
+    /// `2020`
+    Year(i32),
+    /// `11.2020` or `2020-11`, year and month
+    YearMonth(i32, u32),
+    /// any of the `date_formats()`
+    Full(NaiveDate),
+}
+
+#[cfg(feature = "date_parsing")]
+impl PartialDate {
+    /// Interprets `"2020"`, `"11.2020"`, `"11/2020"` or `"2020-11"`, full dates are left out.
+    fn parse(date: &str) -> Option<PartialDate> {
+        let number = |digits: &str| -> Option<u32> {
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            digits.parse().ok()
+        };
+        let date = date.trim();
+        let (year, month) = if let Some((year, month)) = date.split_once('-') {
+            (year, Some(month))
+        } else if let Some((month, year)) = date.split_once(['.', '/']) {
+            (year, Some(month))
+        } else {
+            (date, None)
+        };
+        let year = number(year).filter(|year| (1..=9999).contains(year))? as i32;
+        match month {
+            None => Some(PartialDate::Year(year)),
+            Some(month) => number(month)
+                .filter(|month| (1..=12).contains(month))
+                .map(|month| PartialDate::YearMonth(year, month)),
+        }
+    }
+}
+
+/// Lowercases and drops `_` and `-`, so camel, snake and kebab case look alike.
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
 
 /// Enables access to structured data via a simple path
 ///
@@ -17,20 +236,62 @@ pub trait PathFinder {
     /// You only need to implement this.
     fn data(&self) -> &Yaml;
 
+    /// Nodes above `data()`, starting at the document root.
+    ///
+    /// Only needed for scoped finders, so that `..` can leave the scope.
+    fn ancestors(&self) -> Vec<&Yaml> {
+        Vec::new()
+    }
+
+    /// How hash keys are compared, exactly unless overridden.
+    fn key_matching(&self) -> KeyMatching {
+        KeyMatching::Exact
+    }
+
+    /// Locale that `get_localized()` falls back to, `en` unless overridden.
+    fn default_locale(&self) -> &str {
+        "en"
+    }
+
+    /// Formats `get_date()` tries in order, ISO and then `dd.mm.YYYY` unless overridden.
+    fn date_formats(&self) -> &[DateFormat] {
+        &[DateFormat::Iso, DateFormat::Dmy]
+    }
+
+    /// Looks up keys according to `key_matching` instead.
+    fn matching(&self, key_matching: KeyMatching) -> Matching<'_, Self> {
+        Matching::new(self, key_matching)
+    }
+
+    /// Like `get()`, but also tells which alternative matched.
+    ///
+    /// Useful for deprecation warnings, `offer.date|offer_date` reports `offer_date`
+    /// when only the old key is there.
+    fn get_with_origin<'a, 'p, I: Into<YPaths<'p>>>(
+        &'a self,
+        paths: I,
+    ) -> Option<(&'a Yaml, YPathBuf)> {
+        let paths = paths.into();
+        let (i, trail) = resolve_alternatives(self, &paths, Resolution::First)
+            .into_iter()
+            .next()?;
+        Some((trail.last().copied()?, paths.origin(i)?))
+    }
+
     /// Wrapper around `get_path()`.
     ///
     /// Splits path string
     /// and replaces `Yaml::Null` and `Yaml::BadValue`.
     fn get<'a>(&'a self, paths: &YPaths) -> Option<&'a Yaml> {
-        paths
-            .alternatives()
-            .filter_map(|path| self.get_direct(self.data(), &path))
-            .nth(0)
+        resolve_alternatives(self, paths, Resolution::First)
+            .into_iter()
+            .next()
+            .and_then(|(_, trail)| trail.last().copied())
     }
 
     /// Wrapper around `get_path()`.
     ///
-    /// Splits path string
+    /// Parses path string, returns the first match
     /// and replaces `Yaml::Null` and `Yaml::BadValue`.
     fn get_direct<'a>(&'a self, data: &'a Yaml, path: &YPath) -> Option<&'a Yaml> {
         // TODO: this can be without copying
@@ -39,11 +300,14 @@ pub trait PathFinder {
         //     "paths shouldn't contain whitespaces {:?}",
         //     path
         // );
-        let elements: Vec<&str> = path.elements().collect();
-        match self.get_path(data, &elements) {
-            Some(&Yaml::BadValue) | Some(&Yaml::Null) => None,
-            content => content,
+        let segments: Vec<Segment> = path.segments().collect();
+        if segments.is_empty() {
+            return None;
         }
+        select(self, vec![data], &segments)
+            .into_iter()
+            .filter_map(|trail| trail.last().copied())
+            .find(|content| !matches!(content, Yaml::BadValue | Yaml::Null))
     }
 
     /// Returns content at `path` in the yaml document.
@@ -54,15 +318,15 @@ pub trait PathFinder {
                 // go further into the rabbit hole
                 Yaml::Hash(ref hash) => {
                     if remainder.is_empty() {
-                        hash.get(&Yaml::String(path.to_owned()))
+                        hash_get(hash, path, self.key_matching())
                     } else {
-                        hash.get(&Yaml::String(path.to_owned()))
+                        hash_get(hash, path, self.key_matching())
                             .and_then(|c| self.get_path(c, remainder))
                     }
                 }
                 // interpret component as index
                 Yaml::Array(ref vec) => {
-                    if let Ok(index) = path.parse::<usize>() {
+                    if let Some(index) = array_index(path, vec.len()) {
                         if remainder.is_empty() {
                             vec.get(index)
                         } else {
@@ -80,6 +344,317 @@ pub trait PathFinder {
         }
     }
 
+    /// Returns content at an RFC 6901 JSON Pointer like `/users/0/name`.
+    ///
+    /// Every reference token is taken literally, `~1` stands for `/` and `~0` for `~`.
+    /// Keys match exactly regardless of `key_matching()`, array indices are plain `0`, `1`, ...
+    /// The empty pointer refers to the whole document.
+    fn get_pointer<'a>(&'a self, pointer: &str) -> Option<&'a Yaml> {
+        let content = if pointer.is_empty() {
+            Some(self.data())
+        } else {
+            pointer
+                .strip_prefix('/')?
+                .split('/')
+                .map(|token| token.replace("~1", "/").replace("~0", "~"))
+                .try_fold(self.data(), |data, token| pointer_step(data, &token))
+        };
+        match content {
+            Some(&Yaml::BadValue) | Some(&Yaml::Null) => None,
+            content => content,
+        }
+    }
+
+    /// Returns all matches of all alternatives in `paths`,
+    /// unless they ask for another `Resolution`.
+    ///
+    /// A path like `users.*.name` yields the name of every user,
+    /// `config.**.password` every password at any depth below `config`
+    /// and `users[role=admin].name` the names of all admins.
+    /// `Yaml::Null` and `Yaml::BadValue` are skipped.
+    fn get_all<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> std::vec::IntoIter<&'a Yaml> {
+        resolve_alternatives(self, &paths.into(), Resolution::All)
+            .into_iter()
+            .filter_map(|(_, trail)| trail.last().copied())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Whether any of `paths` is present, even if it is an explicit `~`.
+    fn exists<'a, I: Into<YPaths<'a>>>(&self, paths: I) -> bool {
+        paths
+            .into()
+            .segment_lists()
+            .iter()
+            .any(|segments| !resolve_with_null(self, segments).is_empty())
+    }
+
+    /// Whether any of `paths` is present and not `~`.
+    fn exists_non_null<'a, I: Into<YPaths<'a>>>(&self, paths: I) -> bool {
+        self.get(&paths.into()).is_some()
+    }
+
+    /// Tells the shape of the first of `paths` that is present, to pick the right getter.
+    fn type_of<'a, I: Into<YPaths<'a>>>(&self, paths: I) -> YamlType {
+        let node = paths.into().segment_lists().iter().find_map(|segments| {
+            resolve_with_null(self, segments)
+                .into_iter()
+                .next()
+                .and_then(|trail| trail.last().copied())
+        });
+        match node {
+            Some(Yaml::String(_)) => YamlType::Str,
+            Some(Yaml::Integer(_)) => YamlType::Int,
+            Some(Yaml::Real(_)) => YamlType::Float,
+            Some(Yaml::Boolean(_)) => YamlType::Bool,
+            Some(Yaml::Hash(_)) => YamlType::Hash,
+            Some(Yaml::Array(_)) => YamlType::Array,
+            Some(Yaml::Null) => YamlType::Null,
+            Some(Yaml::Alias(_)) | Some(Yaml::BadValue) | None => YamlType::Missing,
+        }
+    }
+
+    /// Like `get_all()`, but yields the concrete path of every match along with it.
+    ///
+    /// Paths start at the document root, for scoped finders they are anchored with `/`.
+    fn find_all<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        paths: I,
+    ) -> std::vec::IntoIter<(YPathBuf, &'a Yaml)> {
+        resolve_alternatives(self, &paths.into(), Resolution::All)
+            .into_iter()
+            .filter_map(|(_, trail)| located(self, &trail))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Finds the first element of the array at `path` that satisfies `predicate`, with its path.
+    fn find_where<'a, F, I>(
+        &'a self,
+        path: I,
+        mut predicate: F,
+    ) -> FieldResult<Option<(YPathBuf, &'a Yaml)>>
+    where
+        F: FnMut(&Yaml) -> bool,
+        I: Into<YPaths<'a>>,
+    {
+        let (path, vec) = located_array(self, &path.into())?;
+        Ok(vec
+            .iter()
+            .enumerate()
+            .find(|(_, element)| predicate(element))
+            .map(|(i, element)| (&path / i, element)))
+    }
+
+    /// Like `find_where()`, but returns every element that satisfies `predicate`.
+    fn find_all_where<'a, F, I>(
+        &'a self,
+        path: I,
+        mut predicate: F,
+    ) -> FieldResult<Vec<(YPathBuf, &'a Yaml)>>
+    where
+        F: FnMut(&Yaml) -> bool,
+        I: Into<YPaths<'a>>,
+    {
+        let (path, vec) = located_array(self, &path.into())?;
+        Ok(vec
+            .iter()
+            .enumerate()
+            .filter(|(_, element)| predicate(element))
+            .map(|(i, element)| (&path / i, element))
+            .collect())
+    }
+
+    /// Gets `subpath` of every element of the array at `path`, like every `email` of `users`.
+    ///
+    /// Elements without `subpath` are `Missing` in place, so the result lines up with the array.
+    fn pluck<'a, 's, I, S>(&'a self, path: I, subpath: S) -> FieldResult<Vec<FieldResult<&'a Yaml>>>
+    where
+        I: Into<YPaths<'a>>,
+        S: Into<YPath<'s>>,
+    {
+        let subpath = subpath.into();
+        let (_, vec) = located_array(self, &path.into())?;
+        Ok(vec
+            .iter()
+            .map(|element| {
+                self.get_direct(element, &subpath)
+                    .ok_or(FieldError::Missing)
+            })
+            .collect())
+    }
+
+    /// Adds up the numbers at `paths`, see `numbers()`.
+    fn sum_f64<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> FieldResult<f64> {
+        Ok(self.numbers(paths)?.into_iter().sum())
+    }
+
+    /// Smallest of the numbers at `paths`, see `numbers()`.
+    fn min_f64<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> FieldResult<f64> {
+        self.numbers(paths)?
+            .into_iter()
+            .reduce(f64::min)
+            .ok_or_else(|| FieldError::invalid("no numbers to aggregate"))
+    }
+
+    /// Largest of the numbers at `paths`, see `numbers()`.
+    fn max_f64<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> FieldResult<f64> {
+        self.numbers(paths)?
+            .into_iter()
+            .reduce(f64::max)
+            .ok_or_else(|| FieldError::invalid("no numbers to aggregate"))
+    }
+
+    /// Mean of the numbers at `paths`, see `numbers()`.
+    fn avg_f64<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> FieldResult<f64> {
+        let numbers = self.numbers(paths)?;
+        if numbers.is_empty() {
+            return Err(FieldError::invalid("no numbers to aggregate"));
+        }
+        Ok(numbers.iter().sum::<f64>() / numbers.len() as f64)
+    }
+
+    /// Collects numbers to aggregate, the error names the first element that isn't one.
+    ///
+    /// A path to a single array like `amounts` takes its elements,
+    /// otherwise every match counts, so `items.*.price` plucks the prices.
+    fn numbers<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> FieldResult<Vec<f64>> {
+        elements_or_matches(self, paths.into())?
+            .into_iter()
+            .map(|(path, node)| {
+                node.as_f64()
+                    .or_else(|| node.as_i64().map(|i| i as f64))
+                    .ok_or_else(|| {
+                        FieldError::Invalid(format!("{} is not a number ({:?})", path, node))
+                    })
+            })
+            .collect()
+    }
+
+    /// Distinct scalars at `paths` in the order they first appear, like every tag in use.
+    ///
+    /// Like `numbers()`, a single array takes its elements, otherwise every match counts.
+    fn unique_values<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> FieldResult<Vec<&'a Yaml>> {
+        let mut seen = HashSet::new();
+        let mut unique = Vec::new();
+        for (path, node) in elements_or_matches(self, paths.into())? {
+            if matches!(node, Yaml::Hash(_) | Yaml::Array(_)) {
+                return Err(FieldError::Invalid(format!("{} is not a scalar", path)));
+            }
+            if seen.insert(node) {
+                unique.push(node);
+            }
+        }
+        Ok(unique)
+    }
+
+    /// Groups the elements of the array at `path` by their scalar at `key_subpath`.
+    ///
+    /// Groups keep the document order, an element without a scalar key is an error.
+    fn group_by<'a, 's, I, S>(
+        &'a self,
+        path: I,
+        key_subpath: S,
+    ) -> FieldResult<BTreeMap<String, Vec<&'a Yaml>>>
+    where
+        I: Into<YPaths<'a>>,
+        S: Into<YPath<'s>>,
+    {
+        let key_subpath = key_subpath.into();
+        let (_, vec) = located_array(self, &path.into())?;
+        let mut groups: BTreeMap<String, Vec<&Yaml>> = BTreeMap::new();
+        for (i, element) in vec.iter().enumerate() {
+            let key = element_scalar(self, i, element, &key_subpath)?;
+            let key = scalar_text(key)
+                .map(Cow::into_owned)
+                .or_else(|| key.as_bool().map(|b| b.to_string()))
+                .unwrap_or_default();
+            groups.entry(key).or_default().push(element);
+        }
+        Ok(groups)
+    }
+
+    /// Elements of the array at `path` ordered by their scalar at `subpath`, the document stays as is.
+    ///
+    /// Numbers compare numerically and come before strings, equal elements keep their order.
+    fn sorted_by<'a, 's, I, S>(&'a self, path: I, subpath: S) -> FieldResult<Vec<&'a Yaml>>
+    where
+        I: Into<YPaths<'a>>,
+        S: Into<YPath<'s>>,
+    {
+        let subpath = subpath.into();
+        let (_, vec) = located_array(self, &path.into())?;
+        let mut keyed = vec
+            .iter()
+            .enumerate()
+            .map(|(i, element)| Ok((element_scalar(self, i, element, &subpath)?, element)))
+            .collect::<FieldResult<Vec<_>>>()?;
+        keyed.sort_by(|(a, _), (b, _)| compare_scalars(a, b));
+        Ok(keyed.into_iter().map(|(_, element)| element).collect())
+    }
+
+    /// Gets the first element of the array at `path`.
+    ///
+    /// Unlike `rows.first`, which is just missing, an empty array or a non-array is invalid.
+    fn get_first<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<&'a Yaml> {
+        let (_, vec) = located_array(self, &path.into())?;
+        vec.first()
+            .ok_or_else(|| FieldError::invalid("array is empty"))
+    }
+
+    /// Gets the last element of the array at `path`, an empty array is invalid.
+    fn get_last<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<&'a Yaml> {
+        let (_, vec) = located_array(self, &path.into())?;
+        vec.last()
+            .ok_or_else(|| FieldError::invalid("array is empty"))
+    }
+
+    /// Pairs up the elements of two parallel arrays, which have to be of the same length.
+    fn zip<'a, A, B>(&'a self, path_a: A, path_b: B) -> FieldResult<Vec<(&'a Yaml, &'a Yaml)>>
+    where
+        A: Into<YPaths<'a>>,
+        B: Into<YPaths<'a>>,
+    {
+        let (path_a, a) = located_array(self, &path_a.into())?;
+        let (path_b, b) = located_array(self, &path_b.into())?;
+        if a.len() != b.len() {
+            return Err(FieldError::Invalid(format!(
+                "{} has {} elements, but {} has {}",
+                path_a,
+                a.len(),
+                path_b,
+                b.len()
+            )));
+        }
+        Ok(a.iter().zip(b).collect())
+    }
+
+    /// Narrows the finder down to the first match of `paths`.
+    ///
+    /// Paths are then resolved relative to that node,
+    /// `..` leads back up to its parent.
+    fn scoped<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> Option<Scope<'a>> {
+        resolve_alternatives(self, &paths.into(), Resolution::First)
+            .into_iter()
+            .next()
+            .map(|(_, trail)| {
+                Scope::new(
+                    trail,
+                    self.key_matching(),
+                    self.default_locale(),
+                    self.date_formats(),
+                )
+            })
+    }
+
+    /// Collects all matches of `paths` into a new `Yaml::Array`.
+    ///
+    /// Meant for projections like `users[].name`,
+    /// whose values don't exist contiguously in the document.
+    fn project<'a, I: Into<YPaths<'a>>>(&'a self, paths: I) -> Yaml {
+        Yaml::Array(self.get_all(paths).cloned().collect())
+    }
+
     /// Gets the field for a given path.
     fn field<'a, T, F, I: Into<YPaths<'a>>>(
         &'a self,
@@ -115,144 +690,3615 @@ pub trait PathFinder {
             .map(Into::into)
     }
 
-    /// Gets an `Int` value.
-    ///
-    /// Same mentality as `yaml_rust`, only returns `Some`, if it's a `Yaml::Int`.
-    fn get_int<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<i64> {
-        self.field(path, "not an integer", Yaml::as_i64)
+    /// Gets a `&str` value that has to be one of `allowed`, failures list the allowed set.
+    fn get_str_in<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+        allowed: &[&str],
+    ) -> FieldResult<&'a str> {
+        self.field(path, &format!("not one of {}", allowed.join(", ")), |y| {
+            y.as_str().filter(|s| allowed.contains(s))
+        })
     }
 
-    /// Gets a Date in `dd.mm.YYYY` format.
-    #[cfg(feature = "date_parsing")]
-    fn get_dmy<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Date<Utc>> {
-        self.field(path, "not a date", |x| x.as_str().and_then(parse_dmy_date))
+    /// Like `get_str()`, but a missing value is `Ok(None)`.
+    fn get_opt_str<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Option<&'a str>> {
+        self.get_str(path).maybe()
     }
 
-    /// Gets a `Bool` value.
+    /// Like `get_str()`, but a missing value is `default`.
+    fn get_str_or<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+        default: &'a str,
+    ) -> FieldResult<&'a str> {
+        self.get_str(path).if_missing_try(|| Ok(default))
+    }
+
+    /// Gets the translation of a text stored as `description: {de: ..., en: ...}`.
     ///
-    /// **Careful** this is a bit sweeter then ordinary `YAML1.2`,
-    /// this will interpret `"yes"` and `"no"` as booleans, similar to `YAML1.1`.
-    /// Actually it will interpret any string but `"yes"` als `false`.
-    fn get_bool<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<bool> {
-        self.field(path, "not a boolean", |y| {
-            y.as_bool()
-                // allowing it to be a str: "yes" or "no"
-                .or_else(|| {
-                    y.as_str()
-                        .map(|yes_or_no| match yes_or_no.to_lowercase().as_ref() {
-                            "yes" => true,
-                            _ => false,
-                        })
-                })
+    /// Tries `description.de`, then the `default_locale()`, then `description` as plain string.
+    fn get_localized<'a, P: Into<YPath<'a>>>(
+        &'a self,
+        path: P,
+        locale: &str,
+    ) -> FieldResult<&'a str> {
+        let path = path.into();
+        let candidates = [
+            path.join(locale),
+            path.join(self.default_locale()),
+            YPathBuf::from(path),
+        ];
+        match candidates
+            .iter()
+            .find_map(|candidate| self.get(&YPaths::from(candidate)))
+        {
+            None => Err(FieldError::Missing),
+            Some(Yaml::String(text)) => Ok(text),
+            Some(node) => Err(FieldError::Invalid(format!("not a string ({:?})", node))),
+        }
+    }
+
+    /// Gets a `&str` value without surrounding whitespace.
+    fn get_trimmed_str<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<&'a str> {
+        self.field(path, "not a string", |y| y.as_str().map(str::trim))
+    }
+
+    /// Splits a string, typically a `|` block scalar, into its lines.
+    fn get_lines<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Vec<&'a str>> {
+        self.field(path, "not a string", |y| {
+            y.as_str().map(|s| s.lines().collect())
         })
     }
 
-    /// Get as `Bool` value.
-    fn get_bool_strict<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<bool> {
-        self.field(path, "not a boolean", |y| y.as_bool())
+    /// Gets a list that is written either as an array of strings or as `"a, b, c"`.
+    fn get_csv_list<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Vec<&'a str>> {
+        self.field(path, "neither a list nor comma separated", |y| match y {
+            Yaml::String(s) => Some(
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .collect(),
+            ),
+            Yaml::Array(items) => items.iter().map(Yaml::as_str).collect(),
+            _ => None,
+        })
     }
 
-    /// Get as `Yaml::Hash`
-    fn get_hash<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<&'a YamlHash> {
-        self.field(path, "not a hash", Yaml::as_hash)
+    /// Gets a string, numbers and booleans are rendered as they were written.
+    ///
+    /// `version: 1.0` is a float to YAML, but comes back as `"1.0"` here.
+    fn get_string_coerced<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<String> {
+        self.field(path, "not a scalar", |y| match y {
+            Yaml::Boolean(b) => Some(b.to_string()),
+            _ => scalar_text(y).map(Cow::into_owned),
+        })
     }
 
-    /// Get as `Yaml::Array`
-    fn get_vec<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<&'a YamlArray> {
-        self.field(path, "not a vector", Yaml::as_vec)
+    /// Gets a `Yaml::String` of exactly one character, like a delimiter.
+    fn get_char<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<char> {
+        self.field(path, "not a single character", |y| {
+            let mut chars = y.as_str()?.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        })
     }
 
-    /// Gets a `Float` value.
+    /// Gets a string that looks like an email address.
     ///
-    /// Also takes a `Yaml::I64` and reinterprets it.
-    fn get_f64<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<f64> {
-        self.field(path, "not a float", |y| {
-            y.as_f64().or_else(|| y.as_i64().map(|y| y as f64))
+    /// Only the shape is checked, a dot-atom local part and a domain of hostname labels.
+    fn get_email<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<&'a str> {
+        self.field(path, "not an email address", |y| {
+            y.as_str().filter(|address| is_email(address))
         })
     }
-}
 
-impl PathFinder for yaml_rust::Yaml {
-    fn data(&self) -> &yaml_rust::Yaml {
-        self
+    /// Gets a color like `"#ff8800"`, `"#f80"` or `"rgb(255, 136, 0)"` as red, green and blue.
+    fn get_color<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<(u8, u8, u8)> {
+        self.field(path, "not a color", |y| y.as_str().and_then(parse_color))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::util::parse;
+    /// Decodes a base64 string, line breaks in block scalars are fine.
+    fn get_base64<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Vec<u8>> {
+        self.field(path, "not base64", |y| y.as_str().and_then(decode_base64))
+    }
 
-    struct TestProvider {
-        yaml: Yaml,
+    /// Decodes a `!!binary` scalar.
+    ///
+    /// `yaml_rust` drops tags while loading, so the tag itself can't be checked,
+    /// untagged base64 strings are decoded just the same.
+    fn get_binary<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Vec<u8>> {
+        self.get_base64(path)
     }
 
-    impl TestProvider {
-        pub fn parse(src: &str) -> Self {
-            Self {
-                yaml: parse(src).unwrap(),
-            }
-        }
+    /// Decodes a hex string, with or without `0x` and separators like `:`.
+    ///
+    /// Unquoted `0x...` is an integer to YAML, so it has to be quoted.
+    fn get_hex<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Vec<u8>> {
+        self.field(path, "not hex", |y| y.as_str().and_then(decode_hex))
     }
 
-    impl PathFinder for TestProvider {
-        fn data(&self) -> &Yaml {
-            &self.yaml
-        }
+    /// Gets an `Int` value.
+    ///
+    /// Same mentality as `yaml_rust`, only returns `Some`, if it's a `Yaml::Int`.
+    fn get_int<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<i64> {
+        self.field(path, "not an integer", Yaml::as_i64)
     }
 
-    static NO_FALLBACK_PATH: &'static str = r#"
-    offer:
-        date: 07.11.2019
-    "#;
+    /// Like `get_int()`, but also parses quoted numbers like `"42"`.
+    fn get_int_lenient<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<i64> {
+        self.field(path, "not an integer", |y| {
+            y.as_i64().or_else(|| y.as_str()?.trim().parse().ok())
+        })
+    }
 
-    static FALLBACK_PATH: &'static str = r#"
-    offer_date: 08.11.2019
-    "#;
+    /// Gets an `Int` value within `range`, like `1..=65535` for a port.
+    fn get_int_in_range<'a, R, I>(&'a self, path: I, range: R) -> FieldResult<i64>
+    where
+        R: RangeBounds<i64> + fmt::Debug,
+        I: Into<YPaths<'a>>,
+    {
+        let value = self.get_int(path)?;
+        if range.contains(&value) {
+            Ok(value)
+        } else {
+            Err(FieldError::Invalid(format!(
+                "{} is not in range {:?}",
+                value, range
+            )))
+        }
+    }
 
-    #[test]
-    fn find_fallback_paths() {
-        let no_fallback = TestProvider::parse(NO_FALLBACK_PATH);
-        let fallback = TestProvider::parse(FALLBACK_PATH);
+    /// Like `get_int()`, but a missing value is `Ok(None)`.
+    fn get_opt_int<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Option<i64>> {
+        self.get_int(path).maybe()
+    }
 
-        assert_eq!(
-            no_fallback.get_str("offer.date|offer_date"),
-            FieldResult::Ok("07.11.2019")
-        );
+    /// Like `get_int()`, but a missing value is computed by `default`.
+    fn get_int_or_else<'a, F, I>(&'a self, path: I, default: F) -> FieldResult<i64>
+    where
+        F: FnOnce() -> i64,
+        I: Into<YPaths<'a>>,
+    {
+        self.get_int(path).if_missing_try(|| Ok(default()))
+    }
+
+    /// Gets a non-negative `Int` value.
+    fn get_u64<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<u64> {
+        self.field(path, "not an unsigned integer", |y| {
+            y.as_i64().and_then(|i| u64::try_from(i).ok())
+        })
+    }
+
+    /// Gets an `Int` value that fits into a `u32`.
+    fn get_u32<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<u32> {
+        self.field(path, "not a 32 bit unsigned integer", |y| {
+            y.as_i64().and_then(|i| u32::try_from(i).ok())
+        })
+    }
+
+    /// Gets a non-negative `Int` value for counts, limits and indices.
+    fn get_usize<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<usize> {
+        self.field(path, "not a size", |y| {
+            y.as_i64().and_then(|i| usize::try_from(i).ok())
+        })
+    }
+
+    /// Parses a scalar with `FromStr`.
+    ///
+    /// Numbers are parsed from their literal text and never go through `f64`,
+    /// so types like `rust_decimal::Decimal` keep every digit.
+    /// The parser's error ends up in `FieldError::Invalid`,
+    /// which makes it a good fit for `regex::Regex` as well.
+    fn get_parsed<'a, T, I>(&'a self, path: I) -> FieldResult<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+        I: Into<YPaths<'a>>,
+    {
+        let node = self.get(&path.into()).ok_or(FieldError::Missing)?;
+        let invalid = |reason: String| {
+            FieldError::Invalid(format!("not a {}{} ({:?})", type_name::<T>(), reason, node))
+        };
+        scalar_text(node)
+            .ok_or_else(|| invalid(String::new()))?
+            .parse()
+            .map_err(|e| invalid(format!(": {}", e)))
+    }
+
+    /// Parses a string into an enum with `FromStr`, failures list the allowed `variants`.
+    fn get_enum<'a, T: FromStr, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+        variants: &[&str],
+    ) -> FieldResult<T> {
+        self.field(path, &format!("not one of {}", variants.join(", ")), |y| {
+            y.as_str()?.parse().ok()
+        })
+    }
+
+    /// Gets an integer too large for `i64`, like a 128 bit ID.
+    ///
+    /// Takes numbers as well as strings of digits.
+    fn get_i128<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<i128> {
+        self.field(path, "not a 128 bit integer", |y| {
+            scalar_text(y)?.parse().ok()
+        })
+    }
+
+    /// Gets a non-negative integer too large for `u64`, see `get_i128()`.
+    fn get_u128<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<u128> {
+        self.field(path, "not a 128 bit unsigned integer", |y| {
+            scalar_text(y)?.parse().ok()
+        })
+    }
+
+    /// Gets a duration like `"1h30m"` or `"250ms"`, plain numbers count as seconds.
+    fn get_duration<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Duration> {
+        self.field(path, "not a duration", |y| {
+            scalar_text(y).and_then(|text| parse_duration(&text))
+        })
+    }
+
+    /// Gets an IPv4 or IPv6 address like `"127.0.0.1"` or `"::1"`.
+    fn get_ip_addr<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<IpAddr> {
+        self.field(path, "not an IP address", |y| y.as_str()?.parse().ok())
+    }
+
+    /// Gets an address with port like `"0.0.0.0:8080"` or `"[::1]:443"`.
+    ///
+    /// Host names are not resolved, they are invalid here.
+    fn get_socket_addr<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<SocketAddr> {
+        self.field(path, "not a socket address", |y| y.as_str()?.parse().ok())
+    }
+
+    /// Gets a Date in `dd.mm.YYYY` format.
+    ///
+    /// Prefer `get_dmy_naive()`, chrono has deprecated `Date<Utc>`.
+    #[cfg(feature = "date_parsing")]
+    fn get_dmy<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Date<Utc>> {
+        self.field(path, "not a date", |x| x.as_str().and_then(parse_dmy_date))
+    }
+
+    /// Gets a `NaiveDate` in `dd.mm.YYYY` format, the value carries no timezone anyway.
+    #[cfg(feature = "date_parsing")]
+    fn get_dmy_naive<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<NaiveDate> {
+        self.field(path, "not a date", |x| {
+            x.as_str().and_then(parse_dmy_naive_date)
+        })
+    }
+
+    /// Gets an RFC 3339 datetime like `2019-11-07T13:45:00+01:00`.
+    ///
+    /// YAML's own timestamp forms like `2001-12-14 21:59:43.10 -5` or `2002-12-14` work too.
+    #[cfg(feature = "date_parsing")]
+    fn get_datetime<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+    ) -> FieldResult<DateTime<FixedOffset>> {
+        self.field(path, "not a datetime", |y| {
+            y.as_str().and_then(parse_datetime)
+        })
+    }
+
+    /// Gets a date in the first of `date_formats()` that fits, along with that format.
+    #[cfg(feature = "date_parsing")]
+    fn get_date<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+    ) -> FieldResult<(NaiveDate, DateFormat)> {
+        self.get_date_in(path, self.date_formats())
+    }
+
+    /// Like `get_date()`, but tries the given `formats` in order.
+    #[cfg(feature = "date_parsing")]
+    fn get_date_in<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+        formats: &[DateFormat],
+    ) -> FieldResult<(NaiveDate, DateFormat)> {
+        self.field(path, &format!("not a date in {:?}", formats), |y| {
+            let date = y.as_str()?;
+            formats
+                .iter()
+                .find_map(|&format| Some((format.parse(date)?, format)))
+        })
+    }
+
+    /// Gets a date that may lack the day or month, like `2020`, `11.2020` or `2020-11`.
+    ///
+    /// Full dates are read with `date_formats()`.
+    #[cfg(feature = "date_parsing")]
+    fn get_partial_date<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<PartialDate> {
+        let formats = self.date_formats();
+        self.field(path, "not a partial date", |y| {
+            // `11.2020` and `2020` are numbers to YAML
+            let date = scalar_text(y)?;
+            PartialDate::parse(&date).or_else(|| {
+                formats
+                    .iter()
+                    .find_map(|format| format.parse(&date))
+                    .map(PartialDate::Full)
+            })
+        })
+    }
+
+    /// Gets a unix epoch like `1573130700` or `"1573130700000"`.
+    ///
+    /// Epochs beyond eleven digits are taken as milliseconds, shorter ones as seconds,
+    /// use `get_timestamp_in()` for millisecond epochs before 1973.
+    #[cfg(feature = "date_parsing")]
+    fn get_timestamp<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<DateTime<Utc>> {
+        self.get_timestamp_in(path, EpochUnit::Auto)
+    }
+
+    /// Like `get_timestamp()`, but reads the epoch in the given `unit`.
+    #[cfg(feature = "date_parsing")]
+    fn get_timestamp_in<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+        unit: EpochUnit,
+    ) -> FieldResult<DateTime<Utc>> {
+        self.field(path, "not a timestamp", |y| {
+            y.as_i64()
+                .or_else(|| y.as_str()?.trim().parse().ok())
+                .and_then(|epoch| unit.parse(epoch))
+        })
+    }
+
+    /// Gets a `Bool` value.
+    ///
+    /// **Careful** this is a bit sweeter then ordinary `YAML1.2`,
+    /// this will also interpret `yes/no`, `on/off` and `true/false` strings as booleans,
+    /// case-insensitively, similar to `YAML1.1`. Any other string is invalid.
+    fn get_bool<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<bool> {
+        self.get_bool_as(path, BoolStyle::Words)
+    }
+
+    /// Get as `Bool` value.
+    fn get_bool_strict<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<bool> {
+        self.get_bool_as(path, BoolStyle::Strict)
+    }
+
+    /// Gets a `Bool` value, reading strings according to `style`.
+    fn get_bool_as<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+        style: BoolStyle,
+    ) -> FieldResult<bool> {
+        self.field(path, "not a boolean", |y| {
+            y.as_bool()
+                .or_else(|| y.as_str().and_then(|word| style.interpret(word)))
+        })
+    }
+
+    /// Like `get_bool()`, but a missing value is `Ok(None)`.
+    fn get_opt_bool<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Option<bool>> {
+        self.get_bool(path).maybe()
+    }
+
+    /// Get as `Yaml::Hash`
+    fn get_hash<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<&'a YamlHash> {
+        self.field(path, "not a hash", Yaml::as_hash)
+    }
+
+    /// Get as `Yaml::Array`
+    fn get_vec<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<&'a YamlArray> {
+        self.field(path, "not a vector", Yaml::as_vec)
+    }
+
+    /// Gets a `Yaml::Array` of strings.
+    ///
+    /// The error names the first element that isn't one.
+    fn get_strs<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Vec<&'a str>> {
+        self.get_vec(path)?
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                item.as_str().ok_or_else(|| {
+                    FieldError::Invalid(format!("element {} is not a string ({:?})", i, item))
+                })
+            })
+            .collect()
+    }
+
+    /// Gets a `Yaml::Array` and parses every element with `parser`.
+    ///
+    /// The error names the first element that `parser` rejects.
+    fn get_vec_of<'a, T, F, I>(&'a self, path: I, parser: F) -> FieldResult<Vec<T>>
+    where
+        F: Fn(&'a Yaml) -> Option<T>,
+        I: Into<YPaths<'a>>,
+    {
+        self.get_vec(path)?
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                parser(item).ok_or_else(|| {
+                    FieldError::Invalid(format!("element {} is invalid ({:?})", i, item))
+                })
+            })
+            .collect()
+    }
+
+    /// Walks a `Yaml::Hash` as pairs of string keys and values.
+    ///
+    /// Other keys are either skipped or an error, depending on `skip_non_string_keys`.
+    fn iter_hash<'a, I: Into<YPaths<'a>>>(
+        &'a self,
+        path: I,
+        skip_non_string_keys: bool,
+    ) -> FieldResult<std::vec::IntoIter<(&'a str, &'a Yaml)>> {
+        let mut pairs = Vec::new();
+        for (key, value) in self.get_hash(path)? {
+            match key.as_str() {
+                Some(key) => pairs.push((key, value)),
+                None if skip_non_string_keys => {}
+                None => {
+                    return Err(FieldError::Invalid(format!(
+                        "key {:?} is not a string",
+                        key
+                    )));
+                }
+            }
+        }
+        Ok(pairs.into_iter())
+    }
+
+    /// Counts the elements of an array, the entries of a hash or the characters of a string.
+    fn len<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<usize> {
+        self.field(path, "neither array, hash nor string", |y| match y {
+            Yaml::Array(vec) => Some(vec.len()),
+            Yaml::Hash(hash) => Some(hash.len()),
+            Yaml::String(string) => Some(string.chars().count()),
+            _ => None,
+        })
+    }
+
+    /// Yields the full path of every scalar in the document, in document order.
+    ///
+    /// Explicit `~`s count as scalars, empty arrays and hashes have no leaves.
+    fn leaf_paths(&self) -> std::vec::IntoIter<YPathBuf> {
+        let mut leaves = Vec::new();
+        collect_leaves(self.data(), Vec::new(), &mut leaves);
+        leaves
+            .into_iter()
+            .map(|(path, _)| YPathBuf::from(leaf_path(&path, IndexStyle::Dotted).as_str()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Visits every node in document order, starting with `data()` at the empty path.
+    fn walk<'a, V: Visitor<'a>>(&'a self, visitor: &mut V) {
+        walk_node(self.data(), YPathBuf::new(), visitor);
+    }
+
+    /// Paths of every node equal to `value`, like every place an ID is referenced.
+    fn paths_of(&self, value: &Yaml) -> Vec<YPathBuf> {
+        self.paths_where(|node| node == value)
+    }
+
+    /// Paths of every node that satisfies `predicate`, in document order.
+    ///
+    /// The paths are relative to `data()`, which itself is at the empty path.
+    fn paths_where<F: FnMut(&Yaml) -> bool>(&self, predicate: F) -> Vec<YPathBuf> {
+        let mut matches = Matches {
+            predicate,
+            paths: Vec::new(),
+        };
+        self.walk(&mut matches);
+        matches.paths
+    }
+
+    /// Every scalar in the document along with its path, in the order of `leaf_paths()`.
+    ///
+    /// Array indices are written as `ports.0` or `ports[0]`, according to `index_style`.
+    /// Keys like `true` and `"true"` in the same hash both show up, under the same path.
+    fn flatten(&self, index_style: IndexStyle) -> Vec<(String, &Yaml)> {
+        let mut leaves = Vec::new();
+        collect_leaves(self.data(), Vec::new(), &mut leaves);
+        leaves
+            .into_iter()
+            .map(|(path, value)| (leaf_path(&path, index_style), value))
+            .collect()
+    }
+
+    /// Whether `path` is effectively unset: missing, `~`, `""`, `[]` or `{}`.
+    ///
+    /// Unless `strict`, a string of only whitespace counts as empty too.
+    fn is_empty<'a, I: Into<YPaths<'a>>>(&self, path: I, strict: bool) -> bool {
+        match self.get(&path.into()) {
+            None => true,
+            Some(Yaml::Array(vec)) => vec.is_empty(),
+            Some(Yaml::Hash(hash)) => hash.is_empty(),
+            Some(Yaml::String(string)) if strict => string.is_empty(),
+            Some(Yaml::String(string)) => string.trim().is_empty(),
+            Some(_) => false,
+        }
+    }
+
+    /// Gets the keys of a `Yaml::Hash` in document order, they all have to be strings.
+    fn keys<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Vec<&'a str>> {
+        Ok(self.iter_hash(path, false)?.map(|(key, _)| key).collect())
+    }
+
+    /// Gets a `Yaml::Hash` with string keys and parses every value with `parser`.
+    ///
+    /// The error names the first key that isn't a string or whose value `parser` rejects.
+    fn get_map_of<'a, T, F, I>(&'a self, path: I, parser: F) -> FieldResult<BTreeMap<String, T>>
+    where
+        F: Fn(&'a Yaml) -> Option<T>,
+        I: Into<YPaths<'a>>,
+    {
+        self.get_hash(path)?
+            .iter()
+            .map(|(key, value)| {
+                let key = key
+                    .as_str()
+                    .ok_or_else(|| FieldError::Invalid(format!("key {:?} is not a string", key)))?;
+                let parsed = parser(value).ok_or_else(|| {
+                    FieldError::Invalid(format!("value of {} is invalid ({:?})", key, value))
+                })?;
+                Ok((key.to_owned(), parsed))
+            })
+            .collect()
+    }
+
+    /// Gets a `Float` value.
+    ///
+    /// Also takes a `Yaml::I64` and reinterprets it.
+    fn get_f64<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<f64> {
+        self.field(path, "not a float", |y| {
+            y.as_f64().or_else(|| y.as_i64().map(|y| y as f64))
+        })
+    }
+
+    /// Like `get_f64()`, but rejects `NaN` and infinities such as `.nan` or `.inf`.
+    fn get_f64_finite<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<f64> {
+        self.field(path, "not a finite float", |y| {
+            y.as_f64()
+                .or_else(|| y.as_i64().map(|y| y as f64))
+                .filter(|f| f.is_finite())
+        })
+    }
+
+    /// Like `get_f64()`, but a missing value is `Ok(None)`.
+    fn get_opt_f64<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<Option<f64>> {
+        self.get_f64(path).maybe()
+    }
+
+    /// Like `get_f64()`, but also parses quoted numbers like `"3.14"`.
+    fn get_f64_lenient<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<f64> {
+        self.field(path, "not a float", |y| {
+            y.as_f64()
+                .or_else(|| y.as_i64().map(|y| y as f64))
+                .or_else(|| y.as_str()?.trim().parse().ok())
+        })
+    }
+
+    /// Like `get_f64()`, but fails if the value overflows an `f32`.
+    fn get_f32<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<f32> {
+        self.field(path, "not a 32 bit float", |y| {
+            let float = y.as_f64().or_else(|| y.as_i64().map(|y| y as f64))?;
+            Some(float as f32).filter(|narrowed| narrowed.is_finite() || !float.is_finite())
+        })
+    }
+
+    /// Like `get_f32()`, but also fails if the value loses precision, like `0.1` does.
+    fn get_f32_exact<'a, I: Into<YPaths<'a>>>(&'a self, path: I) -> FieldResult<f32> {
+        self.field(path, "not an exact 32 bit float", |y| {
+            let float = y.as_f64().or_else(|| y.as_i64().map(|y| y as f64))?;
+            Some(float as f32).filter(|narrowed| f64::from(*narrowed) == float)
+        })
+    }
+}
+
+/// The literal text of a string or number.
+fn scalar_text(data: &Yaml) -> Option<Cow<'_, str>> {
+    match data {
+        Yaml::String(s) | Yaml::Real(s) => Some(Cow::Borrowed(s)),
+        Yaml::Integer(i) => Some(Cow::Owned(i.to_string())),
+        _ => None,
+    }
+}
+
+/// The text a key is addressed by in paths, `None` only for keys that are collections.
+fn key_text(key: &Yaml) -> Option<Cow<'_, str>> {
+    match key {
+        Yaml::Boolean(b) => Some(Cow::Owned(b.to_string())),
+        _ => scalar_text(key),
+    }
+}
+
+/// Every node from the document root down to a match.
+type Trail<'a> = Vec<&'a Yaml>;
+
+/// Resolves the alternatives picked by the resolution of `paths`,
+/// each trail along with the index of its alternative.
+fn resolve_alternatives<'a, P: PathFinder + ?Sized>(
+    finder: &'a P,
+    paths: &YPaths,
+    default: Resolution,
+) -> Vec<(usize, Trail<'a>)> {
+    let lists = paths.segment_lists();
+    let mut resolved = lists
+        .iter()
+        .enumerate()
+        .map(|(i, segments)| (i, resolve(finder, segments)))
+        .filter(|(_, trails)| !trails.is_empty());
+    let picked: Vec<(usize, Vec<Trail>)> = match paths.resolution_or(default) {
+        Resolution::First => resolved.next().into_iter().collect(),
+        Resolution::Last => resolved.next_back().into_iter().collect(),
+        Resolution::All => resolved.collect(),
+    };
+    picked
+        .into_iter()
+        .flat_map(|(i, trails)| trails.into_iter().map(move |trail| (i, trail)))
+        .collect()
+}
+
+/// The end of `trail` along with its path, see `PathFinder::find_all()`.
+fn located<'a, P: PathFinder + ?Sized>(
+    finder: &P,
+    trail: &[&'a Yaml],
+) -> Option<(YPathBuf, &'a Yaml)> {
+    let root = if finder.ancestors().is_empty() {
+        YPathBuf::new()
+    } else {
+        YPathBuf::from("/")
+    };
+    Some((trail_path(trail, root)?, *trail.last()?))
+}
+
+/// The elements of the only match of `paths` if that is an array, otherwise every match.
+fn elements_or_matches<'a, P: PathFinder + ?Sized>(
+    finder: &'a P,
+    paths: YPaths<'a>,
+) -> FieldResult<Vec<(YPathBuf, &'a Yaml)>> {
+    let matches: Vec<(YPathBuf, &Yaml)> = finder.find_all(paths).collect();
+    match &matches[..] {
+        [] => Err(FieldError::Missing),
+        [(path, Yaml::Array(vec))] => Ok(vec
+            .iter()
+            .enumerate()
+            .map(|(i, element)| (path / i, element))
+            .collect()),
+        _ => Ok(matches),
+    }
+}
+
+/// The first array at `paths` along with its path.
+fn located_array<'a, P: PathFinder + ?Sized>(
+    finder: &'a P,
+    paths: &YPaths,
+) -> FieldResult<(YPathBuf, &'a YamlArray)> {
+    let (path, node) = resolve_alternatives(finder, paths, Resolution::First)
+        .into_iter()
+        .next()
+        .and_then(|(_, trail)| located(finder, &trail))
+        .ok_or(FieldError::Missing)?;
+    match node {
+        Yaml::Array(vec) => Ok((path, vec)),
+        _ => Err(FieldError::Invalid(format!("not an array ({:?})", node))),
+    }
+}
+
+/// Appends the keys and indices leading along `trail` to `path`,
+/// `None` if a step can't be written as a path element.
+fn trail_path(trail: &[&Yaml], mut path: YPathBuf) -> Option<YPathBuf> {
+    for pair in trail.windows(2) {
+        let (parent, child) = (pair[0], pair[1]);
+        match parent {
+            Yaml::Hash(hash) => {
+                let (key, _) = hash.iter().find(|(_, value)| std::ptr::eq(*value, child))?;
+                path.push(key_text(key)?);
+            }
+            Yaml::Array(vec) => {
+                path.push(
+                    vec.iter()
+                        .position(|element| std::ptr::eq(element, child))?,
+                );
+            }
+            _ => return None,
+        }
+    }
+    Some(path)
+}
+
+/// Resolves `segments` from the finder's data, skipping `Yaml::Null` and `Yaml::BadValue`.
+fn resolve<'a, P: PathFinder + ?Sized>(finder: &'a P, segments: &[Segment]) -> Vec<Trail<'a>> {
+    resolve_with_null(finder, segments)
+        .into_iter()
+        .filter(|trail| !matches!(trail.last(), Some(Yaml::Null)))
+        .collect()
+}
+
+/// Like `resolve()`, but keeps explicit `Yaml::Null`s.
+fn resolve_with_null<'a, P: PathFinder + ?Sized>(
+    finder: &'a P,
+    segments: &[Segment],
+) -> Vec<Trail<'a>> {
+    // only `/` or `$` lead to the node itself
+    if segments.is_empty() {
+        return Vec::new();
+    }
+    let mut trail = finder.ancestors();
+    trail.push(finder.data());
+    select(finder, trail, segments)
+        .into_iter()
+        .filter(|trail| !matches!(trail.last(), Some(Yaml::BadValue)))
+        .collect()
+}
+
+/// Returns every content at `segments` below the end of `trail`.
+///
+/// Like `PathFinder::get_path()`, but a segment can match more than one node.
+fn select<'a, P: PathFinder + ?Sized>(
+    finder: &'a P,
+    trail: Trail<'a>,
+    segments: &[Segment],
+) -> Vec<Trail<'a>> {
+    let (segment, remainder, data) = match (segments.split_first(), trail.last()) {
+        (Some((segment, remainder)), Some(&data)) => (segment, remainder, data),
+        _ => return vec![trail],
+    };
+    let descend = |child: &'a Yaml, segments: &[Segment]| {
+        let mut trail = trail.clone();
+        trail.push(child);
+        select(finder, trail, segments)
+    };
+    match segment {
+        Segment::Key(key) => finder
+            .get_path(data, &[key])
+            .map(|child| descend(child, remainder))
+            .unwrap_or_default(),
+        Segment::Index(index) => match data {
+            Yaml::Array(vec) => wrap_index(*index, vec.len())
+                .and_then(|index| vec.get(index))
+                .map(|child| descend(child, remainder))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        },
+        Segment::Root => {
+            let mut trail = trail;
+            trail.truncate(1);
+            select(finder, trail, remainder)
+        }
+        Segment::Parent => {
+            if trail.len() < 2 {
+                return Vec::new();
+            }
+            let mut trail = trail;
+            trail.pop();
+            select(finder, trail, remainder)
+        }
+        // fan out over every child
+        Segment::Wildcard => children(data)
+            .into_iter()
+            .flat_map(|child| descend(child, remainder))
+            .collect(),
+        Segment::Union(keys) => keys
+            .iter()
+            .filter_map(|key| finder.get_path(data, &[key]))
+            .flat_map(|child| descend(child, remainder))
+            .collect(),
+        Segment::Pattern(pattern) => match data {
+            Yaml::Hash(hash) => {
+                let matching = finder.key_matching();
+                let pattern = matching.fold(pattern);
+                hash.iter()
+                    .filter(|(key, _)| {
+                        key.as_str()
+                            .is_some_and(|key| matches_pattern(&pattern, &matching.fold(key)))
+                    })
+                    .flat_map(|(_, child)| descend(child, remainder))
+                    .collect()
+            }
+            _ => Vec::new(),
+        },
+        // try the rest right here, then keep descending
+        Segment::Recursive => {
+            let mut found = select(finder, trail.clone(), remainder);
+            found.extend(
+                children(data)
+                    .into_iter()
+                    .flat_map(|child| descend(child, segments)),
+            );
+            found
+        }
+        Segment::Filter(filter) => children(data)
+            .into_iter()
+            .filter(|child| {
+                let mut trail = trail.clone();
+                trail.push(child);
+                matches_filter(finder, trail, filter)
+            })
+            .flat_map(|child| descend(child, remainder))
+            .collect(),
+    }
+}
+
+/// Checks whether any scalar at the filter's path satisfies its comparison.
+fn matches_filter<'a, P: PathFinder + ?Sized>(
+    finder: &'a P,
+    trail: Trail<'a>,
+    filter: &Filter,
+) -> bool {
+    let segments: Vec<Segment> = YPath(filter.path).segments().collect();
+    select(finder, trail, &segments)
+        .into_iter()
+        .filter_map(|trail| trail.last().copied())
+        .any(|found| {
+            compare_scalar(found, &filter.value)
+                .map(|ordering| filter.comparison.accepts(ordering))
+                .unwrap_or(false)
+        })
+}
+
+/// Compares a scalar to the literal `value` of a filter.
+///
+/// Numbers are compared numerically, strings lexicographically.
+fn compare_scalar(data: &Yaml, value: &str) -> Option<Ordering> {
+    match data {
+        Yaml::String(s) => Some(s.as_str().cmp(value)),
+        Yaml::Integer(i) => match value.parse::<i64>() {
+            Ok(value) => Some(i.cmp(&value)),
+            Err(_) => (*i as f64).partial_cmp(&value.parse().ok()?),
+        },
+        Yaml::Real(_) => data.as_f64()?.partial_cmp(&value.parse().ok()?),
+        Yaml::Boolean(b) => Some(b.cmp(&value.parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// Orders two scalars, numbers before booleans before strings.
+fn compare_scalars(a: &Yaml, b: &Yaml) -> Ordering {
+    let rank = |y: &Yaml| match y {
+        Yaml::Integer(_) | Yaml::Real(_) => 0,
+        Yaml::Boolean(_) => 1,
+        _ => 2,
+    };
+    let number = |y: &Yaml| y.as_f64().or_else(|| y.as_i64().map(|i| i as f64));
+    match (a, b) {
+        (Yaml::Integer(a), Yaml::Integer(b)) => a.cmp(b),
+        (Yaml::Boolean(a), Yaml::Boolean(b)) => a.cmp(b),
+        (Yaml::String(a), Yaml::String(b)) => a.cmp(b),
+        _ => match (number(a), number(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => rank(a).cmp(&rank(b)),
+        },
+    }
+}
+
+/// The scalar at `subpath` of the `i`th element of an array, see `PathFinder::group_by()`.
+fn element_scalar<'a, P: PathFinder + ?Sized>(
+    finder: &'a P,
+    i: usize,
+    element: &'a Yaml,
+    subpath: &YPath,
+) -> FieldResult<&'a Yaml> {
+    finder
+        .get_direct(element, subpath)
+        .filter(|field| !matches!(field, Yaml::Hash(_) | Yaml::Array(_)))
+        .ok_or_else(|| FieldError::Invalid(format!("element {} has no scalar {}", i, subpath)))
+}
+
+/// Follows one JSON Pointer reference token.
+///
+/// Array tokens have to be `0` or digits without a leading zero, as RFC 6901 demands.
+fn pointer_step<'a>(data: &'a Yaml, token: &str) -> Option<&'a Yaml> {
+    match data {
+        Yaml::Hash(hash) => hash_get(hash, token, KeyMatching::Exact),
+        Yaml::Array(vec) => {
+            let canonical = token == "0"
+                || (!token.is_empty()
+                    && !token.starts_with('0')
+                    && token.bytes().all(|b| b.is_ascii_digit()));
+            if canonical {
+                vec.get(token.parse::<usize>().ok()?)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Looks up `key` in a hash.
+///
+/// Keys are usually strings, but YAML allows integers, booleans and floats as well,
+/// so those are tried if there is no string key.
+fn hash_get<'a>(hash: &'a YamlHash, key: &str, matching: KeyMatching) -> Option<&'a Yaml> {
+    hash.get(&Yaml::String(key.to_owned()))
+        .or_else(|| hash.get(&Yaml::Integer(key.parse().ok()?)))
+        .or_else(|| hash.get(&Yaml::Boolean(key.parse().ok()?)))
+        .or_else(|| {
+            key.parse::<f64>().ok()?;
+            hash.get(&Yaml::Real(key.to_owned()))
+        })
+        .or_else(|| match matching {
+            KeyMatching::Exact => None,
+            _ => hash
+                .iter()
+                .find(|(candidate, _)| {
+                    candidate
+                        .as_str()
+                        .is_some_and(|candidate| matching.matches(key, candidate))
+                })
+                .map(|(_, value)| value),
+        })
+}
+
+/// Interprets a path element as index into an array of length `len`.
+///
+/// Negative indices count from the back, `-1` being the last element.
+/// `first` and `last` may be used as well.
+fn array_index(element: &str, len: usize) -> Option<usize> {
+    let index = match element {
+        "first" => 0,
+        "last" => -1,
+        _ => element.parse::<isize>().ok()?,
+    };
+    wrap_index(index, len)
+}
+
+/// Counts negative indices from the end.
+fn wrap_index(index: isize, len: usize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs())
+    } else {
+        Some(index as usize)
+    }
+}
+
+/// A leaf's path, as keys and indices.
+type LeafPath<'a> = Vec<Segment<'a>>;
+
+/// Collects every scalar below `data`, `path` being where `data` itself is.
+fn collect_leaves<'a>(
+    data: &'a Yaml,
+    path: LeafPath<'a>,
+    leaves: &mut Vec<(LeafPath<'a>, &'a Yaml)>,
+) {
+    let descend = |segment: Segment<'a>, child: &'a Yaml, leaves: &mut Vec<_>| {
+        let mut path = path.clone();
+        path.push(segment);
+        collect_leaves(child, path, leaves);
+    };
+    match data {
+        Yaml::Hash(hash) => {
+            for (key, value) in hash {
+                // keys that are collections can't be part of a path
+                if let Some(key) = key_text(key) {
+                    descend(Segment::Key(key), value, leaves);
+                }
+            }
+        }
+        Yaml::Array(vec) => {
+            for (i, element) in vec.iter().enumerate() {
+                descend(Segment::Index(i as isize), element, leaves);
+            }
+        }
+        Yaml::BadValue | Yaml::Alias(_) => {}
+        scalar => leaves.push((path, scalar)),
+    }
+}
+
+/// Collects the paths of nodes that satisfy `predicate`.
+struct Matches<F> {
+    predicate: F,
+    paths: Vec<YPathBuf>,
+}
+
+impl<F: FnMut(&Yaml) -> bool> Visitor<'_> for Matches<F> {
+    fn enter(&mut self, path: YPath<'_>, node: &Yaml) -> bool {
+        if (self.predicate)(node) {
+            self.paths.push(path.into());
+        }
+        true
+    }
+}
+
+/// Visits `node` at `path` and everything below it.
+fn walk_node<'a, V: Visitor<'a>>(node: &'a Yaml, path: YPathBuf, visitor: &mut V) {
+    if visitor.enter(path.as_path(), node) {
+        match node {
+            Yaml::Hash(hash) => {
+                for (key, value) in hash {
+                    if let Some(key) = key_text(key) {
+                        walk_node(value, &path / key, visitor);
+                    }
+                }
+            }
+            Yaml::Array(vec) => {
+                for (i, element) in vec.iter().enumerate() {
+                    walk_node(element, &path / i, visitor);
+                }
+            }
+            _ => {}
+        }
+    }
+    visitor.leave(path.as_path(), node);
+}
+
+/// Writes a leaf's path with indices according to `style`.
+fn leaf_path(segments: &[Segment], style: IndexStyle) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        match (segment, style) {
+            (Segment::Index(index), IndexStyle::Bracketed) => {
+                path.push_str(&format!("[{}]", index));
+                continue;
+            }
+            _ if !path.is_empty() => path.push('.'),
+            _ => {}
+        }
+        match segment {
+            Segment::Key(key) => path.push_str(&escape(key)),
+            Segment::Index(index) => path.push_str(&index.to_string()),
+            _ => {}
+        }
+    }
+    path
+}
+
+/// Values of a hash or elements of an array.
+fn children(data: &Yaml) -> Vec<&Yaml> {
+    match data {
+        Yaml::Hash(hash) => hash.values().collect(),
+        Yaml::Array(vec) => vec.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl PathFinder for yaml_rust::Yaml {
+    fn data(&self) -> &yaml_rust::Yaml {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse;
+
+    struct TestProvider {
+        yaml: Yaml,
+    }
+
+    impl TestProvider {
+        pub fn parse(src: &str) -> Self {
+            Self {
+                yaml: parse(src).unwrap(),
+            }
+        }
+    }
+
+    impl PathFinder for TestProvider {
+        fn data(&self) -> &Yaml {
+            &self.yaml
+        }
+    }
+
+    static NO_FALLBACK_PATH: &str = r#"
+    offer:
+        date: 07.11.2019
+    "#;
+
+    static FALLBACK_PATH: &str = r#"
+    offer_date: 08.11.2019
+    "#;
+
+    #[test]
+    fn find_fallback_paths() {
+        let no_fallback = TestProvider::parse(NO_FALLBACK_PATH);
+        let fallback = TestProvider::parse(FALLBACK_PATH);
+
+        assert_eq!(
+            no_fallback.get_str("offer.date|offer_date"),
+            FieldResult::Ok("07.11.2019")
+        );
+
+        assert_eq!(
+            fallback.get_str("offer.date|offer_date"),
+            FieldResult::Ok("08.11.2019")
+        );
+
+        assert_eq!(
+            no_fallback.get_str("offer.date"),
+            FieldResult::Ok("07.11.2019")
+        );
+
+        assert_eq!(
+            fallback.get_str("offer_date"),
+            FieldResult::Ok("08.11.2019")
+        );
+
+        assert_eq!(
+            no_fallback.get_str("offer_date"),
+            FieldResult::Err(FieldError::Missing)
+        );
+
+        assert_eq!(
+            fallback.get_str("offer.date"),
+            FieldResult::Err(FieldError::Missing)
+        );
+    }
+
+    #[test]
+    fn empty_alternatives_are_missing() {
+        let fallback = TestProvider::parse(FALLBACK_PATH);
+        assert_eq!(fallback.get_hash("missing|"), Err(FieldError::Missing));
+        assert_eq!(fallback.get_hash(""), Err(FieldError::Missing));
+        assert!(!fallback.exists(""));
+        assert!(fallback.get_hash("/").is_ok());
+
+        let scalar = TestProvider::parse("just text");
+        assert_eq!(scalar.get_str(""), Err(FieldError::Missing));
+        assert_eq!(scalar.get_str("$"), Ok("just text"));
+    }
+
+    #[test]
+    fn paths_trim_whitespace_around_alternatives() {
+        let fallback = TestProvider::parse(FALLBACK_PATH);
+        assert_eq!(
+            fallback.get_str("offer.date | offer_date"),
+            FieldResult::Ok("08.11.2019")
+        );
+        assert_eq!(
+            fallback.get_str(" offer.date |\n offer_date "),
+            FieldResult::Ok("08.11.2019")
+        );
+    }
+
+    #[test]
+    fn paths_forbid_whitespaces_within_elements() {
+        let no_fallback = TestProvider::parse(NO_FALLBACK_PATH);
+        assert_eq!(
+            no_fallback.get_str("offer. date"),
+            FieldResult::Err(FieldError::Missing)
+        );
+    }
+
+    static USERS: &str = r#"
+    users:
+        alice:
+            name: Alice
+        bob:
+            name: Bob
+    groups:
+        - name: admins
+        - name: users
+    "#;
+
+    #[test]
+    fn find_wildcard_paths() {
+        let users = TestProvider::parse(USERS);
+
+        let names: Vec<_> = users
+            .get_all("users.*.name")
+            .filter_map(Yaml::as_str)
+            .collect();
+        assert_eq!(names, vec!["Alice", "Bob"]);
+
+        let groups: Vec<_> = users
+            .get_all("groups/*/name")
+            .filter_map(Yaml::as_str)
+            .collect();
+        assert_eq!(groups, vec!["admins", "users"]);
+
+        assert_eq!(users.get_all("users.*.email").count(), 0);
+    }
+
+    static CONFIG: &str = r#"
+    config:
+        password: top
+        database:
+            password: secret
+            replicas:
+                - password: one
+    "#;
+
+    #[test]
+    fn find_recursive_paths() {
+        let config = TestProvider::parse(CONFIG);
+
+        let passwords: Vec<_> = config
+            .get_all("config.**.password")
+            .filter_map(Yaml::as_str)
+            .collect();
+        assert_eq!(passwords, vec!["top", "secret", "one"]);
+
+        assert_eq!(config.get_all("**.replicas.*").count(), 1);
+    }
+
+    #[test]
+    fn find_negative_indices() {
+        let users = TestProvider::parse(USERS);
+
+        assert_eq!(users.get_str("groups.-1.name"), FieldResult::Ok("users"));
+        assert_eq!(users.get_str("groups.-2.name"), FieldResult::Ok("admins"));
+        assert_eq!(
+            users.get_str("groups.-3.name"),
+            FieldResult::Err(FieldError::Missing)
+        );
+    }
+
+    static HOSTS: &str = r#"
+    hosts:
+        server.com:
+            port: 443
+        a/b|c:
+            port: 80
+    "#;
+
+    #[test]
+    fn find_quoted_paths() {
+        let hosts = TestProvider::parse(HOSTS);
+
+        assert_eq!(hosts.get_int(r#"hosts."server.com".port"#), Ok(443));
+        assert_eq!(hosts.get_int(r#"hosts/"a/b|c"/port"#), Ok(80));
+        assert_eq!(
+            hosts.get_int(r#"hosts.server.com.port|hosts."a/b|c".port"#),
+            Ok(80)
+        );
+        assert_eq!(
+            hosts.get_int("hosts.server.com.port"),
+            Err(FieldError::Missing)
+        );
+    }
+
+    #[test]
+    fn find_escaped_paths() {
+        let hosts = TestProvider::parse(HOSTS);
+
+        assert_eq!(hosts.get_int(r"hosts.server\.com.port"), Ok(443));
+        assert_eq!(hosts.get_int(r"hosts.a\/b\|c.port"), Ok(80));
+        assert_eq!(hosts.get_int(r#"hosts."server\.com".port"#), Ok(443));
+    }
+
+    static TEAM: &str = r#"
+    users:
+        - name: alice
+          email: alice@example.com
+          role: admin
+          age: 42
+        - name: bob
+          email: bob@example.com
+          role: dev
+          age: 23
+        - name: carol
+          email: carol@example.com
+          role: admin
+          age: 23
+    "#;
+
+    #[test]
+    fn find_filtered_paths() {
+        let team = TestProvider::parse(TEAM);
+
+        assert_eq!(team.get_str("users[name=bob].email"), Ok("bob@example.com"));
+        assert_eq!(team.get_str("users.[age=23].name"), Ok("bob"));
+        assert_eq!(
+            team.get_str(r#"users[email="carol@example.com"].name"#),
+            Ok("carol")
+        );
+        assert_eq!(
+            team.get_str("users[name=dave].email"),
+            Err(FieldError::Missing)
+        );
+
+        let admins: Vec<_> = team
+            .get_all("users[role=admin].name")
+            .filter_map(Yaml::as_str)
+            .collect();
+        assert_eq!(admins, vec!["alice", "carol"]);
+    }
+
+    static INVOICES: &str = r#"
+    invoices:
+        - id: 1
+          total: 999.99
+        - id: 2
+          total: 1500
+        - id: 3
+          total: 1000
+    "#;
+
+    #[test]
+    fn find_compared_paths() {
+        let invoices = TestProvider::parse(INVOICES);
+        let ids = |path| -> Vec<i64> { invoices.get_all(path).filter_map(Yaml::as_i64).collect() };
+
+        assert_eq!(ids("invoices[total>1000].id"), vec![2]);
+        assert_eq!(ids("invoices[total>=1000].id"), vec![2, 3]);
+        assert_eq!(ids("invoices[total<1000].id"), vec![1]);
+        assert_eq!(ids("invoices[total<=999.99].id"), vec![1]);
+        assert_eq!(ids("invoices[total!=1000].id"), vec![1, 2]);
+        assert_eq!(ids("invoices[total=1000.0].id"), vec![3]);
+
+        let team = TestProvider::parse(TEAM);
+        assert_eq!(team.get_str("users[name>bob].name"), Ok("carol"));
+    }
+
+    #[test]
+    fn find_jsonpaths() {
+        let team = TestProvider::parse(TEAM);
+        let path = crate::jsonpath::parse("$.users[?(@.name == 'bob')].email").unwrap();
+
+        assert_eq!(team.get_str(path.as_ref()), Ok("bob@example.com"));
+    }
+
+    static POINTERS: &str = r#"
+    users:
+        - name: alice
+    a/b:
+        m~n: escaped
+    "*": literal
+    "#;
+
+    #[test]
+    fn find_json_pointers() {
+        let doc = TestProvider::parse(POINTERS);
+
+        assert_eq!(
+            doc.get_pointer("/users/0/name").and_then(Yaml::as_str),
+            Some("alice")
+        );
+        assert_eq!(
+            doc.get_pointer("/a~1b/m~0n").and_then(Yaml::as_str),
+            Some("escaped")
+        );
+        assert_eq!(
+            doc.get_pointer("/*").and_then(Yaml::as_str),
+            Some("literal")
+        );
+        assert_eq!(doc.get_pointer(""), Some(doc.data()));
+        assert_eq!(doc.get_pointer("users/0"), None);
+        assert_eq!(doc.get_pointer("/users/1"), None);
+
+        // only canonical indices and exact keys
+        assert_eq!(doc.get_pointer("/users/-1"), None);
+        assert_eq!(doc.get_pointer("/users/last"), None);
+        assert_eq!(doc.get_pointer("/users/00"), None);
+        assert_eq!(doc.get_pointer("/users/+0"), None);
+        assert_eq!(doc.get_pointer("/users/"), None);
+        assert_eq!(doc.get_pointer("/users/-"), None);
+        let lenient = doc.matching(KeyMatching::CaseInsensitive);
+        assert!(lenient.get_pointer("/users/0").is_some());
+        assert_eq!(lenient.get_pointer("/Users/0"), None);
+    }
+
+    #[test]
+    fn find_projections() {
+        let team = TestProvider::parse(TEAM);
+
+        assert_eq!(
+            team.project("users[].name"),
+            Yaml::Array(vec![
+                Yaml::String("alice".into()),
+                Yaml::String("bob".into()),
+                Yaml::String("carol".into()),
+            ])
+        );
+        assert_eq!(team.project("users[].phone"), Yaml::Array(vec![]));
+    }
+
+    static DEFAULTS: &str = r#"
+    defaults:
+        timeout: 30
+    services:
+        web:
+            timeout: 10
+        db:
+            host: localhost
+    "#;
+
+    #[test]
+    fn find_parent_paths() {
+        let config = TestProvider::parse(DEFAULTS);
+
+        assert_eq!(config.get_str("services/web/../db/host"), Ok("localhost"));
+        assert_eq!(config.get_str("../defaults"), Err(FieldError::Missing));
+
+        let db = config.scoped("services.db").unwrap();
+        assert_eq!(db.get_str("host"), Ok("localhost"));
+        assert_eq!(db.get_int("timeout|../../defaults.timeout"), Ok(30));
+
+        let web = config.scoped("services.web").unwrap();
+        assert_eq!(web.get_int("timeout|../../defaults.timeout"), Ok(10));
+        assert_eq!(web.get_int("../db/../web/timeout"), Ok(10));
+        assert!(config.scoped("services.mail").is_none());
+    }
+
+    #[test]
+    fn find_root_anchored_paths() {
+        let config = TestProvider::parse(DEFAULTS);
+        assert_eq!(config.get_int("/defaults/timeout"), Ok(30));
+        assert_eq!(config.get_int("$.defaults.timeout"), Ok(30));
+
+        let db = config.scoped("services.db").unwrap();
+        assert_eq!(db.get_int("timeout|/defaults/timeout"), Ok(30));
+        assert_eq!(db.get_int("timeout|$.defaults.timeout"), Ok(30));
+        assert_eq!(db.get_str("$/services/db/host"), Ok("localhost"));
+        assert_eq!(db.get_int("/timeout"), Err(FieldError::Missing));
+        assert_eq!(db.get(&"$".into()), Some(config.data()));
+    }
+
+    static JOBS: &str = r#"
+    jobs:
+        build-linux:
+            status: ok
+        build-windows:
+            status: failed
+        test-linux:
+            status: ok
+        "lint*":
+            status: skipped
+    "#;
+
+    #[test]
+    fn find_key_patterns() {
+        let ci = TestProvider::parse(JOBS);
+        let statuses = |path| -> Vec<&str> { ci.get_all(path).filter_map(Yaml::as_str).collect() };
+
+        assert_eq!(statuses("jobs.build-*.status"), vec!["ok", "failed"]);
+        assert_eq!(statuses("jobs.*-linux.status"), vec!["ok", "ok"]);
+        assert_eq!(statuses("jobs.b*d-*s.status"), vec!["failed"]);
+        assert_eq!(statuses(r#"jobs."lint*".status"#), vec!["skipped"]);
+        assert_eq!(statuses("jobs.deploy-*.status"), Vec::<&str>::new());
+    }
+
+    static ROWS: &str = r#"
+    rows:
+        - header
+        - a
+        - b
+        - c
+        - summary
+    "#;
+
+    #[test]
+    fn find_unions() {
+        let table = TestProvider::parse(ROWS);
+        let rows = |path| -> Vec<&str> { table.get_all(path).filter_map(Yaml::as_str).collect() };
+
+        assert_eq!(rows("rows.[0,-1]"), vec!["header", "summary"]);
+        assert_eq!(rows("rows[3, 1, 9]"), vec!["c", "a"]);
+        assert_eq!(rows("rows[2]"), vec!["b"]);
+
+        let team = TestProvider::parse(TEAM);
+        let fields: Vec<_> = team
+            .get_all("users.0.[name,role]")
+            .filter_map(Yaml::as_str)
+            .collect();
+        assert_eq!(fields, vec!["alice", "admin"]);
+    }
+
+    #[test]
+    fn find_template_paths() {
+        let hosts = TestProvider::parse(HOSTS);
+        let template = YPathTemplate("hosts.{host}.port");
+
+        assert_eq!(template.placeholders(), Ok(vec!["host"]));
+
+        let path = template.bind(&[("host", &"server.com")]).unwrap();
+        assert_eq!(path.0, r"hosts.server\.com.port");
+        assert_eq!(hosts.get_int(path.as_ref()), Ok(443));
+
+        let path = template.bind(&[("host", &"a/b|c")]).unwrap();
+        assert_eq!(hosts.get_int(path.as_ref()), Ok(80));
+
+        let users = TestProvider::parse(USERS);
+        let path = YPathTemplate("groups.{index}.name")
+            .bind(&[("index", &1)])
+            .unwrap();
+        assert_eq!(users.get_str(path.as_ref()), Ok("users"));
+
+        assert_eq!(
+            template.bind(&[]).err(),
+            Some(crate::error::TemplateError::Unbound("host".into()))
+        );
+        assert_eq!(
+            YPathTemplate("hosts.{host").placeholders(),
+            Err(crate::error::TemplateError::Unclosed(6))
+        );
+    }
+
+    #[test]
+    fn find_paths_with_custom_separators() {
+        let hosts = TestProvider::parse(HOSTS);
+
+        let mut separators = Separators::new();
+        separators.elements(&['/']);
+        assert_eq!(
+            hosts.get_int(separators.paths("hosts/server.com/port").as_ref()),
+            Ok(443)
+        );
+
+        separators.elements(&['.']).alternatives(&[';']);
+        assert_eq!(
+            hosts.get_int(separators.paths("hosts.a/b|c.port").as_ref()),
+            Ok(80)
+        );
+        assert_eq!(
+            hosts.get_int(
+                separators
+                    .paths("hosts.missing.port;hosts.\"a/b|c\".port")
+                    .as_ref()
+            ),
+            Ok(80)
+        );
+
+        let config = TestProvider::parse(DEFAULTS);
+        let db = config.scoped("services.db").unwrap();
+        separators.elements(&[':']);
+        assert_eq!(
+            db.get_int(separators.paths("timeout;:defaults:timeout").as_ref()),
+            Ok(30)
+        );
+    }
+
+    static PORTS: &str = r#"
+    ports:
+        8080:
+            service: web
+        22:
+            service: ssh
+        "443":
+            service: https
+    flags:
+        true: enabled
+    versions:
+        1.5: legacy
+    "#;
+
+    #[test]
+    fn find_non_string_keys() {
+        let doc = TestProvider::parse(PORTS);
+
+        assert_eq!(doc.get_str("ports.8080.service"), Ok("web"));
+        assert_eq!(doc.get_str("ports/22/service"), Ok("ssh"));
+        assert_eq!(doc.get_str("ports.443.service"), Ok("https"));
+        assert_eq!(doc.get_str("flags.true"), Ok("enabled"));
+        assert_eq!(doc.get_str(r"versions.1\.5"), Ok("legacy"));
+        assert_eq!(doc.get_str("ports.80.service"), Err(FieldError::Missing));
+    }
+
+    #[test]
+    fn find_typed_paths() {
+        let ports = TestProvider::parse(PORTS);
+        let rows = TestProvider::parse(ROWS);
+
+        let mut path = TypedPath::new();
+        path.key("ports").key("8080").key("service");
+        assert_eq!(ports.get_str(&path), Ok("web"));
+        assert_eq!(path.to_string(), "ports.8080.service");
+
+        let mut path = TypedPath::new();
+        path.key("ports").index(0);
+        assert_eq!(ports.get_hash(&path), Err(FieldError::Missing));
+
+        let mut path = TypedPath::new();
+        path.key("rows").index(-1);
+        assert_eq!(rows.get_str(&path), Ok("summary"));
+
+        let mut path = TypedPath::new();
+        path.key("versions").key("1.5");
+        assert_eq!(ports.get_str(&path), Ok("legacy"));
+        assert_eq!(path.to_string(), r"versions.1\.5");
+    }
+
+    #[test]
+    fn inspect_path_segments() {
+        let segments: Vec<Segment> = YPath("/users[name=bob]/../groups.*").segments().collect();
+        assert_eq!(segments.len(), 6);
+        assert_eq!(segments[0], Segment::Root);
+        assert_eq!(segments[1], Segment::Key("users".into()));
+        match &segments[2] {
+            Segment::Filter(filter) => {
+                assert_eq!(filter.path(), YPath("name"));
+                assert_eq!(filter.comparison(), Comparison::Eq);
+                assert_eq!(filter.value(), "bob");
+            }
+            other => panic!("expected a filter, got {:?}", other),
+        }
+        assert_eq!(
+            segments[3..],
+            [
+                Segment::Parent,
+                Segment::Key("groups".into()),
+                Segment::Wildcard
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_path_syntax() {
+        use crate::error::PathParseError::*;
+
+        assert!(YPath::parse("users.*.name").is_ok());
+        assert!(YPath::parse("/").is_ok());
+        assert!(YPath::parse("$.defaults").is_ok());
+        assert!(YPath::parse("services/web/../db").is_ok());
+        assert!(YPath::parse(r#"hosts."server.com".port"#).is_ok());
+        assert!(YPath::parse("invoices[total>10.5].id").is_ok());
+
+        assert_eq!(YPath::parse("").err(), Some(Empty));
+        assert_eq!(YPath::parse("a..b").err(), Some(StraySeparator(2)));
+        assert_eq!(YPath::parse("a/b.").err(), Some(StraySeparator(3)));
+        assert_eq!(YPath::parse(".a").err(), Some(StraySeparator(0)));
+        assert_eq!(YPath::parse("a//b").err(), Some(StraySeparator(2)));
+        assert_eq!(YPath::parse(r#"a."b.c"#).err(), Some(UnclosedQuote(2)));
+        assert_eq!(YPath::parse("a[b=c").err(), Some(UnclosedBracket(1)));
+        assert_eq!(YPath::parse("a]").err(), Some(UnexpectedBracket(1)));
+        assert_eq!(YPath::parse(r"a\").err(), Some(DanglingEscape(1)));
+
+        assert!(YPaths::parse("offer.date | offer_date").is_ok());
+        assert_eq!(YPaths::parse("a||b").err(), Some(EmptyAlternative(2)));
+        assert_eq!(YPaths::parse("a|b..c").err(), Some(StraySeparator(4)));
+    }
+
+    #[test]
+    fn find_checked_paths() {
+        const NAME: YPath<'static> = crate::ypath!("groups.0.name");
+
+        let doc = TestProvider::parse(USERS);
+        assert_eq!(doc.get_str(NAME), Ok("admins"));
+    }
+
+    #[test]
+    #[should_panic(expected = "stray separator in path")]
+    fn checked_paths_reject_typos() {
+        YPath::checked("users..name");
+    }
+
+    #[test]
+    fn find_built_paths() {
+        let doc = TestProvider::parse(HOSTS);
+
+        let mut path = YPathBuf::new();
+        path.push("hosts");
+        path.push("server.com");
+        assert_eq!(path.as_ref(), r"hosts.server\.com");
+        assert_eq!(doc.get_int(&path.join("port")), Ok(443));
+
+        assert!(path.pop());
+        path.push("a/b|c");
+        assert_eq!(doc.get_int(&path.join("port")), Ok(80));
+
+        assert_eq!(path.parent().map(|parent| parent.0), Some("hosts"));
+        assert_eq!(YPath("/hosts").parent().map(|parent| parent.0), Some("/"));
+        assert_eq!(YPath("a/..").parent().map(|parent| parent.0), Some("a"));
+        assert!(YPath("/").parent().is_none());
+
+        let mut rooted = YPathBuf::from("/");
+        rooted.push("hosts");
+        assert_eq!(rooted.as_ref(), "/hosts");
+        assert!(rooted.pop() && !rooted.pop());
+
+        let mut up = YPathBuf::from("a/..");
+        up.push("b");
+        assert_eq!(up.as_ref(), "a/../b");
+        assert_eq!((YPath("..") / "a/b|c").as_ref(), r"../a\/b\|c");
+
+        let server = doc.scoped(r"hosts.server\.com").unwrap();
+        assert_eq!(server.get_int(&(YPath("..") / "a/b|c" / "port")), Ok(80));
+    }
+
+    static ODD_KEYS: &str = r#"
+    "$":
+        a: dollar
+    "":
+        k: empty
+    "$ref": reference
+    "#;
+
+    #[test]
+    fn find_built_paths_with_odd_keys() {
+        let doc = TestProvider::parse(ODD_KEYS);
+
+        let mut dollar = YPathBuf::new();
+        dollar.push("$");
+        dollar.push("a");
+        assert_eq!(dollar.as_ref(), r"\$.a");
+        assert_eq!(doc.get_str(&dollar), Ok("dollar"));
+
+        let mut empty = YPathBuf::new();
+        empty.push("");
+        empty.push("k");
+        assert_eq!(empty.as_ref(), r#""".k"#);
+        assert_eq!(doc.get_str(&empty), Ok("empty"));
+        assert!(empty.pop());
+        assert_eq!(empty.as_ref(), r#""""#);
+
+        assert_eq!(doc.get_str(&YPathBuf::new().join("$ref")), Ok("reference"));
+
+        let mut typed = TypedPath::new();
+        typed.key("").key("k");
+        assert_eq!(typed.to_string(), r#""".k"#);
+
+        for leaf in doc.leaf_paths() {
+            assert!(doc.exists(&leaf), "{} does not resolve", leaf);
+        }
+    }
+
+    #[test]
+    fn find_with_origin() {
+        let no_fallback = TestProvider::parse(NO_FALLBACK_PATH);
+        let fallback = TestProvider::parse(FALLBACK_PATH);
+
+        let (found, origin) = fallback.get_with_origin("offer.date | offer_date").unwrap();
+        assert_eq!(found.as_str(), Some("08.11.2019"));
+        assert_eq!(origin, YPathBuf::from("offer_date"));
+
+        let compiled = YPaths::from("offer/date|offer_date").compile();
+        let (_, origin) = no_fallback.get_with_origin(&compiled).unwrap();
+        assert_eq!(origin.as_ref(), "offer.date");
+
+        assert!(fallback.get_with_origin("offer.time").is_none());
+    }
+
+    #[test]
+    fn find_alternatives_by_resolution() {
+        let doc = TestProvider::parse(USERS);
+        let names = YPaths::from("users.alice.name|users.carol.name|users.bob.name");
+        let all = |paths| -> Vec<&str> { doc.get_all(paths).filter_map(Yaml::as_str).collect() };
+
+        assert_eq!(doc.get_str(names), Ok("Alice"));
+        assert_eq!(doc.get_str(names.resolution(Resolution::Last)), Ok("Bob"));
+        assert_eq!(doc.get_str(names.resolution(Resolution::All)), Ok("Alice"));
+
+        assert_eq!(all(names), ["Alice", "Bob"]);
+        assert_eq!(all(names.resolution(Resolution::First)), ["Alice"]);
+        assert_eq!(all(names.resolution(Resolution::Last)), ["Bob"]);
+
+        let (_, origin) = doc
+            .get_with_origin(names.resolution(Resolution::Last))
+            .unwrap();
+        assert_eq!(origin.as_ref(), "users.bob.name");
+    }
+
+    #[test]
+    fn find_composed_paths() {
+        let config = TestProvider::parse(DEFAULTS);
+
+        let timeout = YPaths::from("services.web.timeout").or("defaults.timeout");
+        assert_eq!(config.get_int(&timeout), Ok(10));
+        let timeout = YPaths::from("services.db.timeout").or("defaults.timeout");
+        assert_eq!(config.get_int(&timeout), Ok(30));
+
+        let defaults = YPaths::from("timeout").under("defaults");
+        let timeout = YPaths::from("web.timeout|db.timeout")
+            .under("services")
+            .or(&defaults);
+        assert_eq!(
+            YPaths::from(&timeout).to_string(),
+            "services.web.timeout|services.db.timeout|defaults.timeout"
+        );
+        assert_eq!(config.get_int(&timeout), Ok(10));
+
+        let anchored = YPaths::from("/defaults.timeout").under("services");
+        assert_eq!(config.get_int(&anchored), Ok(30));
+    }
+
+    #[test]
+    fn find_compiled_paths() {
+        let no_fallback = TestProvider::parse(NO_FALLBACK_PATH);
+        let fallback = TestProvider::parse(FALLBACK_PATH);
+
+        let offer_date = YPaths::from("offer.date|offer_date").compile();
+        assert_eq!(no_fallback.get_str(&offer_date), Ok("07.11.2019"));
+        assert_eq!(fallback.get_str(&offer_date), Ok("08.11.2019"));
+        assert_eq!(
+            YPaths::from(&offer_date).to_string(),
+            "offer.date|offer_date"
+        );
+    }
+
+    #[test]
+    fn find_static_paths() {
+        static OFFER_DATE: LazyPaths = LazyPaths::new("offer.date|offer_date");
+        const DATE: YPaths = YPaths::new("offer.date");
+
+        let fallback = TestProvider::parse(FALLBACK_PATH);
+        assert_eq!(fallback.get_str(&OFFER_DATE), Ok("08.11.2019"));
+        assert_eq!(fallback.get_str(&OFFER_DATE), Ok("08.11.2019"));
+        assert_eq!(fallback.get_str(DATE), Err(FieldError::Missing));
+    }
+
+    #[test]
+    fn find_joined_paths() {
+        let doc = TestProvider::parse(USERS);
+        let users = YPath("users");
+
+        assert_eq!(doc.get_str(&(users / "bob" / "name")), Ok("Bob"));
+        assert_eq!(doc.get_str(&(YPath("groups") / 1 / "name")), Ok("users"));
+
+        let base = YPathBuf::from("hosts");
+        assert_eq!(
+            (&base / "server.com" / "port").as_ref(),
+            r"hosts.server\.com.port"
+        );
+        assert_eq!(base.as_ref(), "hosts");
+    }
+
+    #[test]
+    fn normalize_paths() {
+        let normalized = |path| YPath(path).normalize().to_string();
+
+        assert_eq!(normalized(r#"a//b/../"c""#), "a.c");
+        assert_eq!(normalized("$.users/alice/../bob"), "/users.bob");
+        assert_eq!(normalized(r#"hosts."server.com""#), r"hosts.server\.com");
+        assert_eq!(normalized(r"hosts.server\.com"), r"hosts.server\.com");
+        assert_eq!(normalized("../a/**/../b"), "../a.**/../b");
+        assert_eq!(normalized("a/.."), "");
+    }
+
+    #[test]
+    fn find_resolved_paths() {
+        let config = TestProvider::parse(DEFAULTS);
+        let web = YPath("services.web");
+
+        assert_eq!(
+            config.get_str(&YPath("../db/host").resolve(web)),
+            Ok("localhost")
+        );
+        assert_eq!(config.get_int(&YPath("timeout").resolve(web)), Ok(10));
+        assert_eq!(
+            config.get_int(&YPath("/defaults.timeout").resolve(web)),
+            Ok(30)
+        );
+        assert_eq!(YPath("a").resolve(YPath("")).to_string(), "a");
+    }
+
+    #[test]
+    fn relate_paths() {
+        let port = YPath(r#"hosts/"server.com".port"#);
+
+        assert!(port.starts_with(r"hosts.server\.com"));
+        assert!(port.starts_with("hosts/a/../server\\.com/port"));
+        assert!(!port.starts_with("hosts.server"));
+        assert!(!port.starts_with("/hosts"));
+
+        assert_eq!(
+            port.strip_prefix("hosts").unwrap().as_ref(),
+            r"server\.com.port"
+        );
+        assert_eq!(
+            port.strip_prefix(r#"hosts."server.com""#).unwrap().as_ref(),
+            "port"
+        );
+        assert!(port.strip_prefix("users").is_none());
+
+        assert_eq!(
+            port.common_prefix(r"hosts.server\.com.timeout").as_ref(),
+            r"hosts.server\.com"
+        );
+        assert_eq!(port.common_prefix("users").as_ref(), "");
+    }
+
+    #[test]
+    fn display_and_parse_paths() {
+        assert_eq!(YPath("users/0.name").to_string(), "users.0.name");
+        assert_eq!(YPath("$.defaults/port").to_string(), "/defaults.port");
+        assert_eq!(
+            YPath("services/web/../db").to_string(),
+            "services.web/../db"
+        );
+        assert_eq!(
+            YPath(r#"hosts."server.com"/port"#).to_string(),
+            r#"hosts."server.com".port"#
+        );
+        assert_eq!(
+            YPaths::from("offer.date | offer/date").to_string(),
+            "offer.date|offer.date"
+        );
+        assert_eq!(
+            format!("{:?}", YPaths::from(&["a", "b"])),
+            r#"YPaths(["a", "b"])"#
+        );
+
+        let path: YPathBuf = "users/alice/name".parse().unwrap();
+        assert_eq!(
+            path.to_string().parse(),
+            Ok(YPathBuf::from("users.alice.name"))
+        );
+        assert_eq!(
+            "a..b".parse::<YPathBuf>(),
+            Err(crate::error::PathParseError::StraySeparator(2))
+        );
+        assert!("a||b".parse::<Paths>().is_err());
+
+        let doc = TestProvider::parse(USERS);
+        let paths: Paths = "users.carol.name|users/bob/name".parse().unwrap();
+        assert_eq!(doc.get_str(paths.as_ref()), Ok("Bob"));
+    }
+
+    #[test]
+    fn paths_as_map_keys() {
+        use std::collections::{BTreeMap, HashMap};
+
+        let doc = TestProvider::parse(USERS);
+        let paths = [YPath("users.bob.name"), YPath("users.alice.name")];
+
+        let cache: HashMap<YPath, &str> = paths
+            .iter()
+            .map(|path| (*path, doc.get_str(*path).unwrap()))
+            .collect();
+        assert_eq!(cache[&YPath("users.alice.name")], "Alice");
+
+        let sorted: BTreeMap<YPath, &str> = cache.into_iter().collect();
+        assert_eq!(sorted.values().collect::<Vec<_>>(), [&"Alice", &"Bob"]);
+    }
+
+    static AMOUNTS: &str = r#"
+    invoice:
+        total: 10.50
+        tax: 1.995
+        items: 3
+        id: "340282366920938463463374607431768211455"
+        note: due soon
+    "#;
+
+    #[test]
+    fn find_parsed_scalars() {
+        let invoice = TestProvider::parse(AMOUNTS);
+
+        assert_eq!(
+            invoice.get_parsed::<String, _>("invoice.total"),
+            Ok("10.50".into())
+        );
+        assert_eq!(
+            invoice.get_parsed::<String, _>("invoice.tax"),
+            Ok("1.995".into())
+        );
+        assert_eq!(invoice.get_parsed("invoice.items"), Ok(3u8));
+        assert_eq!(invoice.get_parsed("invoice.id"), Ok(u128::MAX));
+        assert_eq!(
+            invoice.get_parsed::<u32, _>("invoice.note"),
+            Err(FieldError::Invalid(
+                "not a u32: invalid digit found in string (String(\"due soon\"))".into()
+            ))
+        );
+    }
+
+    static LOGGING: &str = r#"
+    logging:
+        level: warn
+        fallback: verbose
+    "#;
+
+    #[test]
+    fn find_enums() {
+        #[derive(Debug, PartialEq)]
+        enum Level {
+            Debug,
+            Info,
+            Warn,
+        }
+
+        impl FromStr for Level {
+            type Err = ();
+
+            fn from_str(s: &str) -> Result<Self, ()> {
+                match s {
+                    "debug" => Ok(Level::Debug),
+                    "info" => Ok(Level::Info),
+                    "warn" => Ok(Level::Warn),
+                    _ => Err(()),
+                }
+            }
+        }
+
+        let logging = TestProvider::parse(LOGGING);
+        let levels = ["debug", "info", "warn"];
+
+        assert_eq!(logging.get_enum("logging.level", &levels), Ok(Level::Warn));
+        assert_eq!(
+            logging.get_enum::<Level, _>("logging.fallback", &levels),
+            Err(FieldError::Invalid(
+                "not one of debug, info, warn (String(\"verbose\"))".into()
+            ))
+        );
+        assert_eq!(
+            logging.get_enum::<Level, _>("logging.format", &levels),
+            Err(FieldError::Missing)
+        );
+    }
+
+    static IDS: &str = r#"
+    ids:
+        quoted: "340282366920938463463374607431768211455"
+        plain: 340282366920938463463374607431768211455
+        negative: -170141183460469231731687303715884105728
+        small: 42
+        fraction: 1.5
+    "#;
+
+    #[test]
+    fn find_large_integers() {
+        let ids = TestProvider::parse(IDS);
+
+        assert_eq!(ids.get_u128("ids.quoted"), Ok(u128::MAX));
+        assert_eq!(ids.get_u128("ids.plain"), Ok(u128::MAX));
+        assert_eq!(ids.get_u128("ids.small"), Ok(42));
+        assert!(ids.get_u128("ids.negative").is_invalid());
+
+        assert_eq!(ids.get_i128("ids.negative"), Ok(i128::MIN));
+        assert!(ids.get_i128("ids.plain").is_invalid());
+        assert_eq!(
+            ids.get_i128("ids.fraction"),
+            Err(FieldError::Invalid(
+                "not a 128 bit integer (Real(\"1.5\"))".into()
+            ))
+        );
+    }
+
+    static TIMEOUTS: &str = r#"
+    timeouts:
+        connect: 250ms
+        request: 1h30m
+        idle: 2 days 4h
+        retry: 90
+        backoff: 1.5
+        tiny: "1us 500ns"
+        negative: -5
+        unitless: 1h30
+        unknown: 3 fortnights
+    "#;
+
+    #[test]
+    fn find_durations() {
+        let timeouts = TestProvider::parse(TIMEOUTS);
+        let duration = |path| timeouts.get_duration(path);
+
+        assert_eq!(duration("timeouts.connect"), Ok(Duration::from_millis(250)));
+        assert_eq!(duration("timeouts.request"), Ok(Duration::from_secs(5400)));
+        assert_eq!(duration("timeouts.idle"), Ok(Duration::from_secs(187_200)));
+        assert_eq!(duration("timeouts.retry"), Ok(Duration::from_secs(90)));
+        assert_eq!(
+            duration("timeouts.backoff"),
+            Ok(Duration::from_millis(1500))
+        );
+        assert_eq!(duration("timeouts.tiny"), Ok(Duration::from_nanos(1500)));
+        assert!(duration("timeouts.negative").is_invalid());
+        assert!(duration("timeouts.unitless").is_invalid());
+        assert!(duration("timeouts.unknown").is_invalid());
+    }
+
+    static LISTENERS: &str = r#"
+    listen:
+        v4: 0.0.0.0:8080
+        v6: "[::1]:443"
+        ip: 10.0.0.1
+        host: localhost:8080
+        port: 99999
+    "#;
+
+    #[test]
+    fn find_addresses() {
+        let listen = TestProvider::parse(LISTENERS);
+
+        assert_eq!(
+            listen.get_ip_addr("listen.ip"),
+            Ok(IpAddr::from([10, 0, 0, 1]))
+        );
+        assert!(listen.get_ip_addr("listen.v4").is_invalid());
+
+        assert_eq!(
+            listen.get_socket_addr("listen.v4"),
+            Ok(SocketAddr::from(([0, 0, 0, 0], 8080)))
+        );
+        assert_eq!(
+            listen.get_socket_addr("listen.v6").map(|addr| addr.port()),
+            Ok(443)
+        );
+        assert!(listen.get_socket_addr("listen.ip").is_invalid());
+        assert!(listen.get_socket_addr("listen.host").is_invalid());
+        assert!(listen.get_socket_addr("listen.port").is_invalid());
+    }
+
+    static DELIMITERS: &str = r#"
+    csv:
+        separator: ";"
+        quote: "„"
+        escape: "\\"
+        newline: "\r\n"
+        padding: ""
+        width: 1
+    "#;
+
+    #[test]
+    fn find_chars() {
+        let csv = TestProvider::parse(DELIMITERS);
+
+        assert_eq!(csv.get_char("csv.separator"), Ok(';'));
+        assert_eq!(csv.get_char("csv.quote"), Ok('„'));
+        assert_eq!(csv.get_char("csv.escape"), Ok('\\'));
+        assert!(csv.get_char("csv.newline").is_invalid());
+        assert!(csv.get_char("csv.padding").is_invalid());
+        assert!(csv.get_char("csv.width").is_invalid());
+    }
+
+    static CONTACTS: &str = r#"
+    contacts:
+        - jane.doe@example.com
+        - billing+2019@sub.example.co.uk
+        - jane@localhost
+        - jane..doe@example.com
+        - "@example.com"
+        - jane@-example.com
+        - jane doe@example.com
+        - jane.example.com
+    "#;
+
+    #[test]
+    fn find_emails() {
+        let contacts = TestProvider::parse(CONTACTS);
+        let valid: Vec<bool> = (0..8)
+            .map(|i| contacts.get_email(&(YPath("contacts") / i)).is_ok())
+            .collect();
+
+        assert_eq!(
+            valid,
+            [true, true, false, false, false, false, false, false]
+        );
+        assert_eq!(
+            contacts.get_email(&(YPath("contacts") / 0)),
+            Ok("jane.doe@example.com")
+        );
+    }
+
+    static THEME: &str = r##"
+    theme:
+        accent: "#ff8800"
+        short: "#F80"
+        function: rgb(255, 136, 0)
+        overflow: rgb(256, 0, 0)
+        alpha: "#ff880080"
+        named: orange
+        signed: "#+1+2+3"
+    "##;
+
+    #[test]
+    fn find_colors() {
+        let theme = TestProvider::parse(THEME);
+
+        assert_eq!(theme.get_color("theme.accent"), Ok((255, 136, 0)));
+        assert_eq!(theme.get_color("theme.short"), Ok((255, 136, 0)));
+        assert_eq!(theme.get_color("theme.function"), Ok((255, 136, 0)));
+        assert!(theme.get_color("theme.overflow").is_invalid());
+        assert!(theme.get_color("theme.alpha").is_invalid());
+        assert!(theme.get_color("theme.named").is_invalid());
+        assert!(theme.get_color("theme.signed").is_invalid());
+    }
+
+    static BLOBS: &str = r#"
+    blobs:
+        padded: aGVsbG8=
+        unpadded: aGVsbG8
+        wrapped: |
+            aGVs
+            bG8h
+        empty: ""
+        truncated: aGVsbG8==
+        garbage: aGVs*G8=
+        number: 42
+    "#;
+
+    #[test]
+    fn find_base64() {
+        let blobs = TestProvider::parse(BLOBS);
+
+        assert_eq!(blobs.get_base64("blobs.padded"), Ok(b"hello".to_vec()));
+        assert_eq!(blobs.get_base64("blobs.unpadded"), Ok(b"hello".to_vec()));
+        assert_eq!(blobs.get_base64("blobs.wrapped"), Ok(b"hello!".to_vec()));
+        assert_eq!(blobs.get_base64("blobs.empty"), Ok(Vec::new()));
+        assert!(blobs.get_base64("blobs.truncated").is_invalid());
+        assert!(blobs.get_base64("blobs.garbage").is_invalid());
+        assert!(blobs.get_base64("blobs.number").is_invalid());
+    }
+
+    static ICONS: &str = r#"
+    icon: !!binary |
+        R0lGODlhAQABAAAAACw=
+    tagged: !!binary aGk=
+    "#;
+
+    #[test]
+    fn find_binary() {
+        let icons = TestProvider::parse(ICONS);
+
+        assert_eq!(icons.get_binary("icon").map(|bytes| bytes.len()), Ok(14));
+        assert_eq!(icons.get_binary("tagged"), Ok(b"hi".to_vec()));
+    }
+
+    static FINGERPRINTS: &str = r#"
+    keys:
+        prefixed: "0xDEADbeef"
+        mac: 00:1a:2b:3c:4d:5e
+        spaced: de ad be ef
+        odd: abc
+        unquoted: 0xdeadbeef
+        garbage: xyz1
+    "#;
+
+    #[test]
+    fn find_hex() {
+        let keys = TestProvider::parse(FINGERPRINTS);
+
+        assert_eq!(
+            keys.get_hex("keys.prefixed"),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            keys.get_hex("keys.mac"),
+            Ok(vec![0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e])
+        );
+        assert_eq!(
+            keys.get_hex("keys.spaced"),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert!(keys.get_hex("keys.odd").is_invalid());
+        assert!(keys.get_hex("keys.unquoted").is_invalid());
+        assert!(keys.get_hex("keys.garbage").is_invalid());
+    }
+
+    static TRANSLATIONS: &str = r#"
+    product:
+        description:
+            de: Ein Buch
+            en: A book
+        title:
+            en: Rust
+        note: Plain text
+        tags:
+            fr: [livre]
+    "#;
+
+    #[test]
+    fn find_localized() {
+        let product = TestProvider::parse(TRANSLATIONS);
+
+        assert_eq!(
+            product.get_localized("product.description", "de"),
+            Ok("Ein Buch")
+        );
+        assert_eq!(
+            product.get_localized("product.description", "fr"),
+            Ok("A book")
+        );
+        assert_eq!(product.get_localized("product.title", "de"), Ok("Rust"));
+        assert_eq!(
+            product.get_localized("product.note", "de"),
+            Ok("Plain text")
+        );
+        assert!(product.get_localized("product.tags", "fr").is_invalid());
+        assert!(product.get_localized("product.price", "de").is_missing());
+
+        let scope = product.scoped("product").unwrap();
+        assert_eq!(scope.get_localized("title", "de"), Ok("Rust"));
+    }
+
+    static SWITCHES: &str = r#"
+    features:
+        logging: true
+        metrics: Yes
+        tracing: OFF
+        caching: "True"
+        typo: ye
+        level: 1
+    "#;
+
+    #[test]
+    fn find_bool() {
+        let switches = TestProvider::parse(SWITCHES);
+
+        assert_eq!(switches.get_bool("features.logging"), Ok(true));
+        assert_eq!(switches.get_bool("features.metrics"), Ok(true));
+        assert_eq!(switches.get_bool("features.tracing"), Ok(false));
+        assert_eq!(switches.get_bool("features.caching"), Ok(true));
+        assert!(switches.get_bool("features.typo").is_invalid());
+        assert!(switches.get_bool("features.level").is_invalid());
+        assert!(switches.get_bool("features.profiling").is_missing());
+
+        assert!(switches.get_bool_strict("features.metrics").is_invalid());
+    }
+
+    static OPTIONS: &str = r#"
+    server:
+        host: example.com
+        port: 8080
+        timeout: soon
+        verbose: off
+    "#;
+
+    #[test]
+    fn find_optional() {
+        let options = TestProvider::parse(OPTIONS);
+
+        assert_eq!(options.get_opt_str("server.host"), Ok(Some("example.com")));
+        assert_eq!(options.get_opt_str("server.proxy"), Ok(None));
+        assert_eq!(options.get_opt_int("server.port"), Ok(Some(8080)));
+        assert_eq!(options.get_opt_int("server.workers"), Ok(None));
+        assert!(options.get_opt_f64("server.timeout").is_invalid());
+        assert_eq!(options.get_opt_bool("server.verbose"), Ok(Some(false)));
+        assert_eq!(options.get_char("server.separator").maybe(), Ok(None));
+        assert!(options.get_u32("server.host").maybe().is_invalid());
+    }
+
+    #[test]
+    fn find_with_defaults() {
+        let options = TestProvider::parse(OPTIONS);
+
+        assert_eq!(
+            options.get_str_or("server.host", "localhost"),
+            Ok("example.com")
+        );
+        assert_eq!(options.get_str_or("server.proxy", "none"), Ok("none"));
+        assert!(options.get_str_or("server.port", "80").is_invalid());
+        assert_eq!(options.get_int_or_else("server.workers", || 4), Ok(4));
+        assert!(options
+            .get_int_or_else("server.timeout", || 30)
+            .is_invalid());
+        assert_eq!(options.get_strs("server.aliases").or_default(), Ok(vec![]));
+        assert!(options.get_u64("server.host").or_default().is_invalid());
+    }
+
+    #[test]
+    fn find_str_in() {
+        let logging = TestProvider::parse(LOGGING);
+        let levels = ["debug", "info", "warn", "error"];
+
+        assert_eq!(logging.get_str_in("logging.level", &levels), Ok("warn"));
+        assert_eq!(
+            logging.get_str_in("logging.fallback", &levels),
+            Err(FieldError::invalid(
+                "not one of debug, info, warn, error (String(\"verbose\"))"
+            ))
+        );
+        assert!(logging.get_str_in("logging.target", &levels).is_missing());
+    }
+
+    #[test]
+    fn find_int_in_range() {
+        let options = TestProvider::parse(OPTIONS);
+
+        assert_eq!(options.get_int_in_range("server.port", 1..=65535), Ok(8080));
+        assert_eq!(
+            options.get_int_in_range("server.port", ..1024),
+            Err(FieldError::invalid("8080 is not in range ..1024"))
+        );
+        assert!(options.get_int_in_range("server.host", 0..).is_invalid());
+        assert!(options
+            .get_int_in_range("server.workers", 1..=64)
+            .is_missing());
+    }
+
+    static MEASUREMENTS: &str = r#"
+    sensor:
+        temperature: 21.5
+        humidity: 40
+        pressure: .nan
+        altitude: -.inf
+        wind: inf
+    "#;
+
+    #[test]
+    fn find_f64_finite() {
+        let measurements = TestProvider::parse(MEASUREMENTS);
+
+        assert_eq!(measurements.get_f64_finite("sensor.temperature"), Ok(21.5));
+        assert_eq!(measurements.get_f64_finite("sensor.humidity"), Ok(40.0));
+        assert!(measurements.get_f64("sensor.pressure").unwrap().is_nan());
+        assert!(measurements.get_f64_finite("sensor.pressure").is_invalid());
+        assert!(measurements.get_f64_finite("sensor.altitude").is_invalid());
+        assert!(measurements.get_f64_finite("sensor.wind").is_invalid());
+        assert!(measurements.get_f64_finite("sensor.light").is_missing());
+    }
+
+    static PRESENCE: &str = r#"
+    account:
+        name: Jane
+        nickname: ~
+        roles: []
+    "#;
+
+    #[test]
+    fn find_exists() {
+        let presence = TestProvider::parse(PRESENCE);
+
+        assert!(presence.exists("account.name"));
+        assert!(presence.exists("account.nickname"));
+        assert!(presence.exists("account.roles"));
+        assert!(presence.exists("account.email|account.name"));
+        assert!(!presence.exists("account.email"));
+        assert!(!presence.exists("account.name.first"));
+
+        assert!(presence.exists_non_null("account.name"));
+        assert!(!presence.exists_non_null("account.nickname"));
+        assert!(!presence.exists_non_null("account.email"));
+    }
+
+    #[test]
+    fn find_type_of() {
+        let presence = TestProvider::parse(PRESENCE);
+        let options = TestProvider::parse(OPTIONS);
+        let measurements = TestProvider::parse(MEASUREMENTS);
+        let switches = TestProvider::parse(SWITCHES);
+
+        assert_eq!(presence.type_of("account"), YamlType::Hash);
+        assert_eq!(presence.type_of("account.name"), YamlType::Str);
+        assert_eq!(presence.type_of("account.nickname"), YamlType::Null);
+        assert_eq!(presence.type_of("account.roles"), YamlType::Array);
+        assert_eq!(presence.type_of("account.email"), YamlType::Missing);
+        assert_eq!(options.type_of("server.port"), YamlType::Int);
+        assert_eq!(options.type_of("server.verbose"), YamlType::Str);
+        assert_eq!(switches.type_of("features.logging"), YamlType::Bool);
+        assert_eq!(measurements.type_of("sensor.temperature"), YamlType::Float);
+    }
+
+    #[test]
+    fn find_keys() {
+        let product = TestProvider::parse(TRANSLATIONS);
+
+        assert_eq!(
+            product.keys("product"),
+            Ok(vec!["description", "title", "note", "tags"])
+        );
+        assert_eq!(product.keys("product.description"), Ok(vec!["de", "en"]));
+        assert!(product.keys("product.note").is_invalid());
+        assert!(product.keys("product.price").is_missing());
+    }
+
+    #[test]
+    fn find_len() {
+        let product = TestProvider::parse(TRANSLATIONS);
+        let presence = TestProvider::parse(PRESENCE);
+
+        assert_eq!(product.len("product"), Ok(4));
+        assert_eq!(product.len("product.tags.fr"), Ok(1));
+        assert_eq!(product.len("product.description.de"), Ok(8));
+        assert_eq!(presence.len("account.roles"), Ok(0));
+        assert!(TestProvider::parse(OPTIONS).len("server.port").is_invalid());
+        assert!(product.len("product.price").is_missing());
+    }
+
+    static SECTIONS: &str = r#"
+    sections:
+        unset: ~
+        blank: ""
+        spaces: "  "
+        list: []
+        map: {}
+        zero: 0
+        filled: [1]
+    "#;
+
+    #[test]
+    fn find_is_empty() {
+        let sections = TestProvider::parse(SECTIONS);
+
+        assert!(sections.is_empty("sections.unset", true));
+        assert!(sections.is_empty("sections.missing", true));
+        assert!(sections.is_empty("sections.blank", true));
+        assert!(sections.is_empty("sections.list", true));
+        assert!(sections.is_empty("sections.map", true));
+        assert!(sections.is_empty("sections.spaces", false));
+        assert!(!sections.is_empty("sections.spaces", true));
+        assert!(!sections.is_empty("sections.zero", false));
+        assert!(!sections.is_empty("sections.filled", false));
+        assert!(!sections.is_empty("sections", false));
+    }
+
+    static AUDITED: &str = r#"
+    server:
+        host: example.com
+        ports: [80, 443]
+        tls: {}
+        "log.level": ~
+    "#;
+
+    #[test]
+    fn find_leaf_paths() {
+        let audited = TestProvider::parse(AUDITED);
+        let leaves: Vec<String> = audited.leaf_paths().map(|p| p.to_string()).collect();
+
+        assert_eq!(
+            leaves,
+            vec![
+                "server.host",
+                "server.ports.0",
+                "server.ports.1",
+                r"server.log\.level",
+            ]
+        );
+        for leaf in audited.leaf_paths() {
+            assert!(audited.exists(&leaf));
+        }
+
+        let ports = TestProvider::parse(PORTS);
+        let leaves: Vec<String> = ports.leaf_paths().map(|p| p.to_string()).collect();
+        assert_eq!(
+            leaves,
+            vec![
+                "ports.8080.service",
+                "ports.22.service",
+                "ports.443.service",
+                "flags.true",
+                r"versions.1\.5",
+            ]
+        );
+        for leaf in ports.leaf_paths() {
+            assert!(ports.exists(&leaf), "{} does not resolve", leaf);
+        }
+    }
+
+    static LISTED: &str = r#"
+    - id: 1
+    - id: 2
+    "#;
+
+    static COUNTED: &str = r#"
+    numbers: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]
+    "#;
+
+    static BOTH_TRUE: &str = r#"
+    flags:
+        true: bool
+        "true": string
+    "#;
+
+    #[test]
+    fn find_flatten() {
+        let audited = TestProvider::parse(AUDITED);
+
+        fn paths(flat: &[(String, &Yaml)]) -> Vec<String> {
+            flat.iter().map(|(path, _)| path.clone()).collect()
+        }
+
+        let dotted = audited.flatten(IndexStyle::Dotted);
+        assert_eq!(
+            paths(&dotted),
+            vec![
+                "server.host",
+                "server.ports.0",
+                "server.ports.1",
+                r"server.log\.level",
+            ]
+        );
+        assert_eq!(
+            dotted[2],
+            ("server.ports.1".to_string(), &Yaml::Integer(443))
+        );
+        assert_eq!(dotted[3].1, &Yaml::Null);
+
+        let bracketed = audited.flatten(IndexStyle::Bracketed);
+        assert_eq!(
+            bracketed[1],
+            ("server.ports[0]".to_string(), &Yaml::Integer(80))
+        );
+        assert_eq!(bracketed.len(), 4);
+
+        let invoices = TestProvider::parse(LISTED);
+        assert_eq!(
+            paths(&invoices.flatten(IndexStyle::Bracketed)),
+            vec!["[0].id", "[1].id"]
+        );
+
+        let ports = TestProvider::parse(PORTS);
+        let flat = ports.flatten(IndexStyle::Dotted);
+        assert_eq!(
+            paths(&flat),
+            ports
+                .leaf_paths()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            flat[3],
+            ("flags.true".to_string(), &Yaml::String("enabled".into()))
+        );
+
+        // numbers don't sort as text
+        let counted = TestProvider::parse(COUNTED);
+        let flat = counted.flatten(IndexStyle::Dotted);
+        assert_eq!(flat.len(), 11);
+        assert_eq!(flat[2], ("numbers.2".to_string(), &Yaml::Integer(2)));
+        assert_eq!(flat[10], ("numbers.10".to_string(), &Yaml::Integer(10)));
+
+        // both `true` keys are kept
+        let both = TestProvider::parse(BOTH_TRUE);
+        assert_eq!(
+            both.flatten(IndexStyle::Dotted),
+            vec![
+                ("flags.true".to_string(), &Yaml::String("bool".into())),
+                ("flags.true".to_string(), &Yaml::String("string".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_walk() {
+        #[derive(Default)]
+        struct Outline {
+            lines: Vec<String>,
+            depth: usize,
+        }
+
+        impl Visitor<'_> for Outline {
+            fn enter(&mut self, path: YPath<'_>, node: &Yaml) -> bool {
+                self.lines
+                    .push(format!("{}{}", "  ".repeat(self.depth), path));
+                self.depth += 1;
+                // don't list the ports one by one
+                !matches!(node, Yaml::Array(_))
+            }
+
+            fn leave(&mut self, _path: YPath<'_>, _node: &Yaml) {
+                self.depth -= 1;
+            }
+        }
+
+        let audited = TestProvider::parse(AUDITED);
+        let mut outline = Outline::default();
+        audited.walk(&mut outline);
+
+        assert_eq!(
+            outline.lines,
+            vec![
+                "",
+                "  server",
+                "    server.host",
+                "    server.ports",
+                "    server.tls",
+                r"    server.log\.level",
+            ]
+        );
+        assert_eq!(outline.depth, 0);
+    }
+
+    #[test]
+    fn find_all_with_paths() {
+        let audited = TestProvider::parse(AUDITED);
+        fn found<'a>(matches: impl Iterator<Item = (YPathBuf, &'a Yaml)>) -> Vec<(String, Yaml)> {
+            matches
+                .map(|(path, value)| (path.to_string(), value.clone()))
+                .collect()
+        }
+
+        assert_eq!(
+            found(audited.find_all("server.ports.*")),
+            vec![
+                ("server.ports.0".to_string(), Yaml::Integer(80)),
+                ("server.ports.1".to_string(), Yaml::Integer(443)),
+            ]
+        );
+        assert_eq!(
+            found(audited.find_all("**.host|server.ports.-1")),
+            vec![
+                (
+                    "server.host".to_string(),
+                    Yaml::String("example.com".into())
+                ),
+                ("server.ports.1".to_string(), Yaml::Integer(443)),
+            ]
+        );
+        assert_eq!(found(audited.find_all("server.tls.*")), vec![]);
+
+        let ports = TestProvider::parse(PORTS);
+        let (path, value) = ports.find_all("flags.true").next().unwrap();
+        assert_eq!(path.to_string(), "flags.true");
+        assert_eq!(ports.get(&(&path).into()), Some(value));
+
+        let server = audited.scoped("server").unwrap();
+        let (path, _) = server.find_all("ports.0").next().unwrap();
+        assert_eq!(path.to_string(), "/server.ports.0");
+        assert_eq!(server.get(&(&path).into()), Some(&Yaml::Integer(80)));
+    }
+
+    static STAFF: &str = r#"
+    staff:
+        - name: Ada
+          role: admin
+        - name: Grace
+          role: editor
+        - name: Linus
+          role: admin
+    "#;
+
+    #[test]
+    fn find_where() {
+        let staff = TestProvider::parse(STAFF);
+        let is_admin = |person: &Yaml| person["role"].as_str() == Some("admin");
+
+        let (path, first) = staff.find_where("staff", is_admin).unwrap().unwrap();
+        assert_eq!(path.to_string(), "staff.0");
+        assert_eq!(first["name"].as_str(), Some("Ada"));
+
+        let admins = staff.find_all_where("staff", is_admin).unwrap();
+        let paths: Vec<String> = admins.iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(paths, vec!["staff.0", "staff.2"]);
+
+        assert_eq!(staff.find_where("staff", |_| false), Ok(None));
+        assert!(staff.find_where("staff.0", is_admin).is_invalid());
+        assert!(staff.find_all_where("guests", is_admin).is_missing());
+    }
+
+    static MEMBERS: &str = r#"
+    members:
+        - name: Ada
+          contact:
+            email: ada@example.com
+        - name: Grace
+        - name: Linus
+          contact:
+            email: linus@example.com
+    "#;
+
+    #[test]
+    fn find_pluck() {
+        let members = TestProvider::parse(MEMBERS);
+
+        let emails = members.pluck("members", "contact.email").unwrap();
+        assert_eq!(emails.len(), 3);
+        assert_eq!(emails[0], Ok(&Yaml::String("ada@example.com".into())));
+        assert!(emails[1].is_missing());
+        assert_eq!(
+            emails[2].as_ref().map(|y| y.as_str()),
+            Ok(Some("linus@example.com"))
+        );
+
+        assert!(members.pluck("members.0", "name").is_invalid());
+        assert!(members.pluck("guests", "name").is_missing());
+    }
+
+    static REFERENCES: &str = r#"
+    products:
+        - id: 4711
+          name: Cologne
+        - id: 42
+          related: [4711]
+    bundles:
+        summer: [42, 4711]
+    "#;
+
+    #[test]
+    fn find_paths_of() {
+        let references = TestProvider::parse(REFERENCES);
+        let strings = |paths: Vec<YPathBuf>| -> Vec<String> {
+            paths.iter().map(ToString::to_string).collect()
+        };
+
+        assert_eq!(
+            strings(references.paths_of(&Yaml::Integer(4711))),
+            vec!["products.0.id", "products.1.related.0", "bundles.summer.1"]
+        );
+        assert_eq!(
+            strings(references.paths_where(|node| node.as_vec().is_some())),
+            vec!["products", "products.1.related", "bundles.summer"]
+        );
+        assert!(references.paths_of(&Yaml::Integer(1)).is_empty());
+
+        let ports = TestProvider::parse(PORTS);
+        assert_eq!(
+            strings(ports.paths_of(&Yaml::String("enabled".into()))),
+            vec!["flags.true"]
+        );
+    }
+
+    static INVOICE: &str = r#"
+    invoice:
+        payments: [100, 50.5, 49.5]
+        refunds: []
+        items:
+            - price: 12.5
+            - price: 7
+            - price: twenty
+    "#;
+
+    #[test]
+    fn find_aggregates() {
+        let invoice = TestProvider::parse(INVOICE);
+
+        assert_eq!(invoice.sum_f64("invoice.payments"), Ok(200.0));
+        assert_eq!(invoice.min_f64("invoice.payments"), Ok(49.5));
+        assert_eq!(invoice.max_f64("invoice.payments"), Ok(100.0));
+        assert_eq!(invoice.avg_f64("invoice.payments.*"), Ok(200.0 / 3.0));
+        assert_eq!(invoice.sum_f64("invoice.items.[0,1].price"), Ok(19.5));
+        assert_eq!(
+            invoice.sum_f64("invoice.items.*.price"),
+            Err(FieldError::invalid(
+                "invoice.items.2.price is not a number (String(\"twenty\"))"
+            ))
+        );
+
+        assert_eq!(invoice.sum_f64("invoice.refunds"), Ok(0.0));
+        assert!(invoice.max_f64("invoice.refunds").is_invalid());
+        assert!(invoice.avg_f64("invoice.discounts").is_missing());
+    }
+
+    #[test]
+    fn find_group_by() {
+        let staff = TestProvider::parse(STAFF);
+        let members = TestProvider::parse(MEMBERS);
+
+        let by_role = staff.group_by("staff", "role").unwrap();
+        let names = |role: &str| -> Vec<&str> {
+            by_role[role]
+                .iter()
+                .filter_map(|person| person["name"].as_str())
+                .collect()
+        };
+        assert_eq!(by_role.keys().collect::<Vec<_>>(), vec!["admin", "editor"]);
+        assert_eq!(names("admin"), vec!["Ada", "Linus"]);
+        assert_eq!(names("editor"), vec!["Grace"]);
+
+        assert_eq!(
+            members.group_by("members", "contact.email"),
+            Err(FieldError::invalid("element 1 has no scalar contact.email"))
+        );
+        assert!(staff.group_by("crew", "role").is_missing());
+    }
+
+    static RELEASES: &str = r#"
+    releases:
+        - name: gamma
+          order: 10
+        - name: alpha
+          order: 2
+        - name: beta
+          order: 2.5
+        - name: delta
+          order: 2
+    "#;
+
+    #[test]
+    fn find_sorted_by() {
+        let releases = TestProvider::parse(RELEASES);
+        let names = |sorted: Vec<&Yaml>| -> Vec<String> {
+            sorted
+                .iter()
+                .filter_map(|release| release["name"].as_str())
+                .map(ToOwned::to_owned)
+                .collect()
+        };
+
+        assert_eq!(
+            names(releases.sorted_by("releases", "order").unwrap()),
+            vec!["alpha", "delta", "beta", "gamma"]
+        );
+        assert_eq!(
+            names(releases.sorted_by("releases", "name").unwrap()),
+            vec!["alpha", "beta", "delta", "gamma"]
+        );
+        assert_eq!(releases.get_str("releases.0.name"), Ok("gamma"));
+        assert!(releases.sorted_by("releases", "date").is_invalid());
+        assert!(releases.sorted_by("drafts", "name").is_missing());
+    }
+
+    #[test]
+    fn find_first_and_last_elements() {
+        let invoice = TestProvider::parse(INVOICE);
 
         assert_eq!(
-            fallback.get_str("offer.date|offer_date"),
-            FieldResult::Ok("08.11.2019")
+            invoice.get_first("invoice.payments"),
+            Ok(&Yaml::Integer(100))
+        );
+        assert_eq!(
+            invoice.get_last("invoice.payments"),
+            Ok(&Yaml::Real("49.5".into()))
+        );
+        assert_eq!(
+            invoice.get_first("invoice.refunds"),
+            Err(FieldError::invalid("array is empty"))
+        );
+        assert_eq!(
+            invoice.get_last("invoice.items.0.price"),
+            Err(FieldError::invalid("not an array (Real(\"12.5\"))"))
         );
+        assert!(invoice.get_first("invoice.discounts").is_missing());
+    }
+
+    static POSTS: &str = r#"
+    posts:
+        - title: Hello
+          tags: [intro, rust]
+        - title: Again
+          tags: [rust, yaml, intro]
+        - title: Empty
+          tags: []
+    colors: [red, green, red, 1, "1"]
+    "#;
+
+    #[test]
+    fn find_unique_values() {
+        let posts = TestProvider::parse(POSTS);
+        let strings = |values: Vec<&Yaml>| -> Vec<String> {
+            values.iter().map(|value| format!("{:?}", value)).collect()
+        };
 
         assert_eq!(
-            no_fallback.get_str("offer.date"),
-            FieldResult::Ok("07.11.2019")
+            strings(posts.unique_values("posts.*.tags.*").unwrap()),
+            vec![
+                r#"String("intro")"#,
+                r#"String("rust")"#,
+                r#"String("yaml")"#
+            ]
+        );
+        assert_eq!(
+            strings(posts.unique_values("colors").unwrap()),
+            vec![
+                r#"String("red")"#,
+                r#"String("green")"#,
+                "Integer(1)",
+                r#"String("1")"#
+            ]
         );
+        assert_eq!(
+            posts.unique_values("posts.*.tags"),
+            Err(FieldError::invalid("posts.0.tags is not a scalar"))
+        );
+        assert!(posts.unique_values("authors").is_missing());
+    }
+
+    static PARALLEL: &str = r#"
+    chart:
+        labels: [Jan, Feb, Mar]
+        values: [3, 5, 8]
+        colors: [red, blue]
+    "#;
+
+    #[test]
+    fn find_zip() {
+        let chart = TestProvider::parse(PARALLEL);
+
+        let points = chart.zip("chart.labels", "chart.values").unwrap();
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[1].0.as_str(), Some("Feb"));
+        assert_eq!(points[1].1.as_i64(), Some(5));
 
         assert_eq!(
-            fallback.get_str("offer_date"),
-            FieldResult::Ok("08.11.2019")
+            chart.zip("chart.labels", "chart.colors"),
+            Err(FieldError::invalid(
+                "chart.labels has 3 elements, but chart.colors has 2"
+            ))
+        );
+        assert!(chart.zip("chart.labels", "chart.labels.0").is_invalid());
+        assert!(chart.zip("chart.labels", "chart.sizes").is_missing());
+    }
+
+    static ANSWERS: &str = r#"
+    survey:
+        newsletter: ja
+        tracking: Nein
+        terms: y
+        cookies: "true"
+    "#;
+
+    #[test]
+    fn find_bool_as() {
+        let answers = TestProvider::parse(ANSWERS);
+        let german = BoolStyle::Custom {
+            truthy: &["ja"],
+            falsy: &["nein"],
+        };
+
+        assert_eq!(answers.get_bool_as("survey.newsletter", german), Ok(true));
+        assert_eq!(answers.get_bool_as("survey.tracking", german), Ok(false));
+        assert!(answers.get_bool_as("survey.cookies", german).is_invalid());
+        assert_eq!(
+            answers.get_bool_as("survey.terms", BoolStyle::Yaml11),
+            Ok(true)
         );
+        assert!(answers
+            .get_bool_as("survey.terms", BoolStyle::Words)
+            .is_invalid());
+        assert!(answers
+            .get_bool_as("survey.cookies", BoolStyle::Strict)
+            .is_invalid());
+    }
+
+    static EDITED: &str = r#"
+    customer:
+        name: "Jane Doe  "
+        city: "\tBerlin\n"
+        country: Germany
+    "#;
+
+    #[test]
+    fn find_trimmed_strings() {
+        let edited = TestProvider::parse(EDITED);
+
+        assert_eq!(edited.get_str("customer.name"), Ok("Jane Doe  "));
+        assert_eq!(edited.get_trimmed_str("customer.name"), Ok("Jane Doe"));
+        assert_eq!(edited.get_trimmed_str("customer.city"), Ok("Berlin"));
+        assert_eq!(edited.get_trimmed_str("customer.country"), Ok("Germany"));
+    }
+
+    static SNIPPETS: &str = r#"
+    address: |
+        Jane Doe
+        Main Street 1
+        12345 Berlin
+    script: |-
+        cargo build
+        cargo test
+    folded: >
+        one
+        line
+    "#;
+
+    #[test]
+    fn find_lines() {
+        let snippets = TestProvider::parse(SNIPPETS);
 
         assert_eq!(
-            no_fallback.get_str("offer_date"),
-            FieldResult::Err(FieldError::Missing)
+            snippets.get_lines("address"),
+            Ok(vec!["Jane Doe", "Main Street 1", "12345 Berlin"])
+        );
+        assert_eq!(
+            snippets.get_lines("script"),
+            Ok(vec!["cargo build", "cargo test"])
         );
+        assert_eq!(snippets.get_lines("folded"), Ok(vec!["one line"]));
+    }
+
+    static RECIPIENTS: &str = r#"
+    joined: alice, bob,carol
+    listed: [alice, bob, carol]
+    single: alice
+    mixed: [alice, 42]
+    "#;
+
+    #[test]
+    fn find_csv_lists() {
+        let recipients = TestProvider::parse(RECIPIENTS);
+        let names = vec!["alice", "bob", "carol"];
+
+        assert_eq!(recipients.get_csv_list("joined"), Ok(names.clone()));
+        assert_eq!(recipients.get_csv_list("listed"), Ok(names));
+        assert_eq!(recipients.get_csv_list("single"), Ok(vec!["alice"]));
+        assert!(recipients.get_csv_list("mixed").is_invalid());
+    }
+
+    #[test]
+    fn find_strs() {
+        let recipients = TestProvider::parse(RECIPIENTS);
 
         assert_eq!(
-            fallback.get_str("offer.date"),
-            FieldResult::Err(FieldError::Missing)
+            recipients.get_strs("listed"),
+            Ok(vec!["alice", "bob", "carol"])
         );
+        assert_eq!(
+            recipients.get_strs("mixed"),
+            Err(FieldError::Invalid(
+                "element 1 is not a string (Integer(42))".into()
+            ))
+        );
+        assert!(recipients.get_strs("joined").is_invalid());
+        assert!(recipients.get_strs("nobody").is_missing());
+    }
+
+    static SAMPLES: &str = r#"
+    ports: [80, 443, 8080]
+    ratios: [0.5, 1, 2.5]
+    broken: [80, http, 443]
+    "#;
+
+    #[test]
+    fn find_vecs_of() {
+        let samples = TestProvider::parse(SAMPLES);
+        let port = |y: &Yaml| y.as_i64().and_then(|i| u16::try_from(i).ok());
+
+        assert_eq!(samples.get_vec_of("ports", port), Ok(vec![80, 443, 8080]));
+        assert_eq!(
+            samples.get_vec_of("ratios", |y| y
+                .as_f64()
+                .or_else(|| y.as_i64().map(|i| i as f64))),
+            Ok(vec![0.5, 1.0, 2.5])
+        );
+        assert_eq!(
+            samples.get_vec_of("broken", port),
+            Err(FieldError::Invalid(
+                "element 1 is invalid (String(\"http\"))".into()
+            ))
+        );
+        assert!(samples.get_vec_of("missing", port).is_missing());
+    }
+
+    static WEIGHTS: &str = r#"
+    weights:
+        alice: 3
+        bob: 5
+    broken:
+        alice: 3
+        bob: heavy
+    numbered:
+        1: 3
+    "#;
+
+    #[test]
+    fn find_maps_of() {
+        let weights = TestProvider::parse(WEIGHTS);
+
+        let map = weights.get_map_of("weights", Yaml::as_i64).unwrap();
+        assert_eq!(
+            map.into_iter().collect::<Vec<_>>(),
+            [("alice".to_owned(), 3), ("bob".to_owned(), 5)]
+        );
+        assert_eq!(
+            weights.get_map_of("broken", Yaml::as_i64),
+            Err(FieldError::Invalid(
+                "value of bob is invalid (String(\"heavy\"))".into()
+            ))
+        );
+        assert_eq!(
+            weights.get_map_of("numbered", Yaml::as_i64),
+            Err(FieldError::Invalid("key Integer(1) is not a string".into()))
+        );
+    }
+
+    #[test]
+    fn iterate_hashes() {
+        let ports = TestProvider::parse(PORTS);
+
+        let services: Vec<&str> = ports
+            .iter_hash("ports", true)
+            .unwrap()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(services, ["443"]);
+        assert!(ports.iter_hash("ports", false).is_invalid());
+
+        let pairs: Vec<(&str, &Yaml)> = ports.iter_hash("ports.443", false).unwrap().collect();
+        assert_eq!(pairs, [("service", &Yaml::String("https".into()))]);
+        assert!(ports.iter_hash("nowhere", true).is_missing());
+    }
+
+    static RELEASE: &str = r#"
+    release:
+        version: 1.0
+        build: 42
+        stable: true
+        name: Ferris
+        tags: [lts]
+    "#;
+
+    #[test]
+    fn find_coerced_strings() {
+        let release = TestProvider::parse(RELEASE);
+
+        assert!(release.get_string("release.version").is_invalid());
+        assert_eq!(
+            release.get_string_coerced("release.version"),
+            Ok("1.0".into())
+        );
+        assert_eq!(release.get_string_coerced("release.build"), Ok("42".into()));
+        assert_eq!(
+            release.get_string_coerced("release.stable"),
+            Ok("true".into())
+        );
+        assert_eq!(
+            release.get_string_coerced("release.name"),
+            Ok("Ferris".into())
+        );
+        assert!(release.get_string_coerced("release.tags").is_invalid());
+    }
+
+    static GENERATED: &str = r#"
+    stats:
+        count: "42"
+        padded: " 7 "
+        ratio: "0.75"
+        plain: 2.5
+        whole: 3
+        label: "forty-two"
+    "#;
+
+    #[test]
+    fn find_quoted_numbers() {
+        let stats = TestProvider::parse(GENERATED);
+
+        assert!(stats.get_int("stats.count").is_invalid());
+        assert_eq!(stats.get_int_lenient("stats.count"), Ok(42));
+        assert_eq!(stats.get_int_lenient("stats.padded"), Ok(7));
+        assert_eq!(stats.get_int_lenient("stats.whole"), Ok(3));
+        assert!(stats.get_int_lenient("stats.ratio").is_invalid());
+        assert!(stats.get_int_lenient("stats.label").is_invalid());
+
+        assert_eq!(stats.get_f64_lenient("stats.ratio"), Ok(0.75));
+        assert_eq!(stats.get_f64_lenient("stats.count"), Ok(42.0));
+        assert_eq!(stats.get_f64_lenient("stats.plain"), Ok(2.5));
+        assert_eq!(stats.get_f64_lenient("stats.whole"), Ok(3.0));
+        assert!(stats.get_f64_lenient("stats.label").is_invalid());
+    }
+
+    static LIMITS: &str = r#"
+    limits:
+        retries: 3
+        negative: -1
+        huge: 5000000000
+        ratio: 0.5
+        tenth: 0.1
+        astronomic: 1e300
+        name: three
+    "#;
+
+    #[test]
+    fn find_unsigned_integers() {
+        let limits = TestProvider::parse(LIMITS);
+
+        assert_eq!(limits.get_u64("limits.retries"), Ok(3));
+        assert_eq!(limits.get_u64("limits.huge"), Ok(5_000_000_000));
+        assert!(limits.get_u64("limits.negative").is_invalid());
+        assert!(limits.get_u64("limits.ratio").is_invalid());
+
+        assert_eq!(limits.get_u32("limits.retries"), Ok(3));
+        assert!(limits.get_u32("limits.huge").is_invalid());
+        assert!(limits.get_u32("limits.negative").is_invalid());
+        assert_eq!(limits.get_u32("limits.timeout"), Err(FieldError::Missing));
+    }
+
+    #[test]
+    fn find_small_floats() {
+        let limits = TestProvider::parse(LIMITS);
+
+        assert_eq!(limits.get_f32("limits.ratio"), Ok(0.5));
+        assert_eq!(limits.get_f32("limits.retries"), Ok(3.0));
+        assert_eq!(limits.get_f32("limits.tenth"), Ok(0.1));
+        assert!(limits.get_f32("limits.astronomic").is_invalid());
+
+        assert_eq!(limits.get_f32_exact("limits.ratio"), Ok(0.5));
+        assert!(limits.get_f32_exact("limits.tenth").is_invalid());
+        assert!(limits.get_f32_exact("limits.astronomic").is_invalid());
+    }
+
+    #[test]
+    fn find_sizes() {
+        let limits = TestProvider::parse(LIMITS);
+
+        assert_eq!(limits.get_usize("limits.retries"), Ok(3));
+        assert!(limits.get_usize("limits.negative").is_invalid());
+        assert!(limits.get_usize("limits.name").is_invalid());
     }
 
+    static CASES: &str = r#"
+    Server:
+        Port: 8080
+        port: 9090
+    "#;
+
+    #[test]
+    fn find_keys_case_insensitively() {
+        let doc = TestProvider::parse(CASES);
+        assert_eq!(doc.get_int("server.port"), Err(FieldError::Missing));
+
+        let lenient = doc.matching(KeyMatching::CaseInsensitive);
+        assert_eq!(lenient.get_int("server.port"), Ok(9090));
+        assert_eq!(lenient.get_int("SERVER.PORT"), Ok(8080));
+        assert_eq!(lenient.scoped("server").unwrap().get_int("PORT"), Ok(8080));
+
+        let ports: Vec<i64> = lenient
+            .get_all("server.po*")
+            .filter_map(Yaml::as_i64)
+            .collect();
+        assert_eq!(ports, vec![8080, 9090]);
+        assert_eq!(doc.get_all("server.po*").count(), 0);
+    }
+
+    static STYLES: &str = r#"
+    client:
+        maxRetries: 3
+        connect-timeout: 10
+        read_timeout: 20
+    "#;
+
+    #[test]
+    fn find_keys_normalized() {
+        let doc = TestProvider::parse(STYLES);
+        assert_eq!(doc.get_int("client.max_retries"), Err(FieldError::Missing));
+
+        let lenient = doc.matching(KeyMatching::Normalized);
+        assert_eq!(lenient.get_int("client.max_retries"), Ok(3));
+        assert_eq!(lenient.get_int("client.max-retries"), Ok(3));
+        assert_eq!(lenient.get_int("client.connectTimeout"), Ok(10));
+        assert_eq!(lenient.get_int("Client.READ_TIMEOUT"), Ok(20));
+        assert_eq!(lenient.get_int("client.timeout"), Err(FieldError::Missing));
+
+        let timeouts: Vec<i64> = lenient
+            .get_all("client.*_TIMEOUT")
+            .filter_map(Yaml::as_i64)
+            .collect();
+        assert_eq!(timeouts, vec![10, 20]);
+    }
+
+    #[test]
+    fn find_first_and_last() {
+        let table = TestProvider::parse(ROWS);
+        assert_eq!(table.get_str("rows.first"), Ok("header"));
+        assert_eq!(table.get_str("rows.last"), Ok("summary"));
+        assert_eq!(table.get_str("rows[first,last]"), Ok("header"));
+
+        let empty = TestProvider::parse("rows: []");
+        assert_eq!(empty.get_str("rows.last"), Err(FieldError::Missing));
+        assert_eq!(empty.get_str("rows.first"), Err(FieldError::Missing));
+    }
+
+    static OFFER_DATE: [&str; 2] = ["offer.date", "offer_date"];
+
     #[test]
-    #[should_panic]
-    fn paths_forbid_whitespaces() {
+    fn find_listed_alternatives() {
+        let no_fallback = TestProvider::parse(NO_FALLBACK_PATH);
         let fallback = TestProvider::parse(FALLBACK_PATH);
+
+        assert_eq!(no_fallback.get_str(&OFFER_DATE), Ok("07.11.2019"));
+        assert_eq!(fallback.get_str(&OFFER_DATE), Ok("08.11.2019"));
+
+        let mut alternatives = vec!["offer.time"];
+        alternatives.push("offer_date");
+        assert_eq!(fallback.get_str(alternatives.as_slice()), Ok("08.11.2019"));
         assert_eq!(
-            fallback.get_str("offer.date | offer_date"),
-            FieldResult::Ok("08.11.2019")
+            fallback.get_str(&["offer.date", "offer|date"]),
+            Err(FieldError::Missing)
+        );
+    }
+
+    #[cfg(feature = "date_parsing")]
+    static EPOCHS: &str = r#"
+    epochs:
+        seconds: 1573130700
+        millis: 1573130700000
+        quoted: " 1573130700 "
+        last_seconds: 99999999999
+        first_millis: 100000000000
+        early_millis: 86400000
+        minimum: -9223372036854775808
+        text: yesterday
+    "#;
+
+    #[cfg(feature = "date_parsing")]
+    #[test]
+    fn find_timestamps() {
+        let epochs = TestProvider::parse(EPOCHS);
+        let at = |millis: i64| Utc.timestamp_millis_opt(millis).unwrap();
+        let november = at(1_573_130_700_000);
+        assert_eq!(november.to_rfc3339(), "2019-11-07T12:45:00+00:00");
+
+        assert_eq!(epochs.get_timestamp("epochs.seconds"), Ok(november));
+        assert_eq!(epochs.get_timestamp("epochs.millis"), Ok(november));
+        assert_eq!(epochs.get_timestamp("epochs.quoted"), Ok(november));
+        assert!(epochs.get_timestamp("epochs.text").is_invalid());
+        assert!(epochs.get_timestamp("epochs.minimum").is_invalid());
+
+        // eleven digits are still seconds, twelve are milliseconds
+        assert_eq!(
+            epochs.get_timestamp("epochs.last_seconds"),
+            Ok(at(99_999_999_999_000))
+        );
+        assert_eq!(
+            epochs.get_timestamp("epochs.first_millis"),
+            Ok(at(100_000_000_000))
+        );
+
+        // milliseconds before 1973 need to be asked for
+        assert_eq!(
+            epochs.get_timestamp("epochs.early_millis"),
+            Ok(at(86_400_000_000))
+        );
+        assert_eq!(
+            epochs.get_timestamp_in("epochs.early_millis", EpochUnit::Millis),
+            Ok(at(86_400_000))
+        );
+        assert_eq!(
+            epochs.get_timestamp_in("epochs.seconds", EpochUnit::Seconds),
+            Ok(november)
+        );
+        assert_eq!(
+            epochs.get_timestamp_in("epochs.seconds", EpochUnit::Millis),
+            Ok(at(1_573_130_700))
+        );
+    }
+
+    #[cfg(feature = "date_parsing")]
+    static TIMES: &str = r#"
+    times:
+        rfc: 2019-11-07T13:45:00+01:00
+        zulu: 2019-11-07T12:45:00Z
+        compact: 2019-11-07 13:45:00 +0100
+        spaced: 2001-12-14 21:59:43.10 -5
+        local: 2001-12-14 21:59:43.10
+        date: 2002-12-14
+        far_east: 2019-11-07T13:45:00+25
+        text: tomorrow
+    "#;
+
+    #[cfg(feature = "date_parsing")]
+    #[test]
+    fn find_datetimes() {
+        let times = TestProvider::parse(TIMES);
+        let rfc = |datetime: &str| DateTime::parse_from_rfc3339(datetime).unwrap();
+        let offset = |path: &str| {
+            times
+                .get_datetime(path)
+                .map(|t| t.offset().local_minus_utc())
+        };
+
+        assert_eq!(
+            times.get_datetime("times.rfc"),
+            Ok(rfc("2019-11-07T13:45:00+01:00"))
+        );
+        assert_eq!(
+            times.get_datetime("times.zulu"),
+            Ok(rfc("2019-11-07T13:45:00+01:00"))
+        );
+        assert_eq!(offset("times.zulu"), Ok(0));
+        assert_eq!(
+            times.get_datetime("times.compact"),
+            Ok(rfc("2019-11-07T13:45:00+01:00"))
+        );
+        assert_eq!(offset("times.compact"), Ok(3600));
+        assert_eq!(
+            times.get_datetime("times.spaced"),
+            Ok(rfc("2001-12-14T21:59:43.10-05:00"))
+        );
+        assert_eq!(offset("times.spaced"), Ok(-5 * 3600));
+
+        // without an offset it's UTC, a plain date is midnight
+        assert_eq!(
+            times.get_datetime("times.local"),
+            Ok(rfc("2001-12-14T21:59:43.10Z"))
+        );
+        assert_eq!(offset("times.local"), Ok(0));
+        assert_eq!(
+            times.get_datetime("times.date"),
+            Ok(rfc("2002-12-14T00:00:00Z"))
+        );
+
+        assert!(times.get_datetime("times.far_east").is_invalid());
+        assert!(times.get_datetime("times.text").is_invalid());
+    }
+
+    #[cfg(feature = "date_parsing")]
+    static HOLIDAYS: &str = r#"
+    holidays:
+        christmas: 25.12.2016
+        short: "25.12"
+        unquoted: 25.12
+        leap: 29.02.2016
+        missing_leap: 29.02.2017
+        ancient: 25.12.1900
+    "#;
+
+    #[cfg(feature = "date_parsing")]
+    #[test]
+    fn find_naive_dates() {
+        let holidays = TestProvider::parse(HOLIDAYS);
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(
+            holidays.get_dmy_naive("holidays.christmas"),
+            Ok(date(2016, 12, 25))
+        );
+        assert_eq!(
+            holidays.get_dmy_naive("holidays.leap"),
+            Ok(date(2016, 2, 29))
+        );
+        assert!(holidays.get_dmy_naive("holidays.short").is_invalid());
+        assert!(holidays.get_dmy_naive("holidays.unquoted").is_invalid());
+        assert!(holidays.get_dmy_naive("holidays.missing_leap").is_invalid());
+        assert!(holidays.get_dmy_naive("holidays.ancient").is_invalid());
+        assert_eq!(
+            holidays.get_dmy_naive("holidays.easter"),
+            Err(FieldError::Missing)
+        );
+    }
+
+    #[cfg(feature = "date_parsing")]
+    static DATES: &str = r#"
+    dates:
+        iso: 2016-12-25
+        dotted: 25.12.2016
+        slashed: 04/05/2016
+        stamp: 2016-12-25T10:00:00+01:00
+        text: someday
+    "#;
+
+    #[cfg(feature = "date_parsing")]
+    #[test]
+    fn find_dates_in_order() {
+        struct American(TestProvider);
+
+        impl PathFinder for American {
+            fn data(&self) -> &Yaml {
+                self.0.data()
+            }
+
+            fn date_formats(&self) -> &[DateFormat] {
+                &[DateFormat::Mdy, DateFormat::Dmy]
+            }
+        }
+
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let dates = TestProvider::parse(DATES);
+        assert_eq!(
+            dates.get_date("dates.iso"),
+            Ok((date(2016, 12, 25), DateFormat::Iso))
+        );
+        assert_eq!(
+            dates.get_date("dates.stamp"),
+            Ok((date(2016, 12, 25), DateFormat::Iso))
+        );
+        assert_eq!(
+            dates.get_date("dates.dotted"),
+            Ok((date(2016, 12, 25), DateFormat::Dmy))
+        );
+        assert_eq!(
+            dates.get_date("dates.slashed"),
+            Ok((date(2016, 5, 4), DateFormat::Dmy))
+        );
+        assert!(dates.get_date("dates.text").is_invalid());
+
+        // the first format that fits wins
+        let american = American(TestProvider::parse(DATES));
+        assert_eq!(
+            american.get_date("dates.slashed"),
+            Ok((date(2016, 4, 5), DateFormat::Mdy))
+        );
+        assert_eq!(
+            american.get_date("dates.dotted"),
+            Ok((date(2016, 12, 25), DateFormat::Dmy))
+        );
+        assert!(american.get_date("dates.iso").is_invalid());
+        assert_eq!(
+            american.scoped("dates").unwrap().get_date("slashed"),
+            Ok((date(2016, 4, 5), DateFormat::Mdy))
+        );
+        assert_eq!(
+            american.get_date_in("dates.iso", &[DateFormat::Ymd]),
+            Ok((date(2016, 12, 25), DateFormat::Ymd))
+        );
+    }
+
+    #[cfg(feature = "date_parsing")]
+    static PLAN: &str = r#"
+    plan:
+        year: 2020
+        month: 11.2020
+        iso_month: 2020-11
+        slashed: 11/2020
+        full: 2020-11-15
+        dotted: 15.11.2020
+        thirteenth: 13.2020
+        text: soon
+    "#;
+
+    #[cfg(feature = "date_parsing")]
+    #[test]
+    fn find_partial_dates() {
+        let plan = TestProvider::parse(PLAN);
+        let full = |y, m, d| PartialDate::Full(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+
+        // `2020` and `11.2020` are numbers to YAML
+        assert_eq!(
+            plan.get_partial_date("plan.year"),
+            Ok(PartialDate::Year(2020))
+        );
+        assert_eq!(
+            plan.get_partial_date("plan.month"),
+            Ok(PartialDate::YearMonth(2020, 11))
+        );
+        assert_eq!(
+            plan.get_partial_date("plan.iso_month"),
+            Ok(PartialDate::YearMonth(2020, 11))
+        );
+        assert_eq!(
+            plan.get_partial_date("plan.slashed"),
+            Ok(PartialDate::YearMonth(2020, 11))
         );
+        assert_eq!(plan.get_partial_date("plan.full"), Ok(full(2020, 11, 15)));
+        assert_eq!(plan.get_partial_date("plan.dotted"), Ok(full(2020, 11, 15)));
+        assert!(plan.get_partial_date("plan.thirteenth").is_invalid());
+        assert!(plan.get_partial_date("plan.text").is_invalid());
     }
 }
@@ -0,0 +1,91 @@
+use yaml_rust::Yaml;
+
+use crate::{DateFormat, KeyMatching, PathFinder};
+
+/// Part of a document, as returned by `PathFinder::scoped()`.
+///
+/// Paths are resolved relative to the scope, `..` steps out of it.
+#[derive(Clone, Debug)]
+pub struct Scope<'a> {
+    /// every node from the document root down to the scope itself
+    trail: Vec<&'a Yaml>,
+    key_matching: KeyMatching,
+    default_locale: &'a str,
+    date_formats: &'a [DateFormat],
+}
+
+impl<'a> Scope<'a> {
+    pub(crate) fn new(
+        trail: Vec<&'a Yaml>,
+        key_matching: KeyMatching,
+        default_locale: &'a str,
+        date_formats: &'a [DateFormat],
+    ) -> Self {
+        debug_assert!(!trail.is_empty(), "a scope needs at least one node");
+        Scope {
+            trail,
+            key_matching,
+            default_locale,
+            date_formats,
+        }
+    }
+}
+
+impl PathFinder for Scope<'_> {
+    fn data(&self) -> &Yaml {
+        self.trail[self.trail.len() - 1]
+    }
+
+    fn ancestors(&self) -> Vec<&Yaml> {
+        self.trail[..self.trail.len() - 1].to_vec()
+    }
+
+    fn key_matching(&self) -> KeyMatching {
+        self.key_matching
+    }
+
+    fn default_locale(&self) -> &str {
+        self.default_locale
+    }
+
+    fn date_formats(&self) -> &[DateFormat] {
+        self.date_formats
+    }
+}
+
+/// A finder with different key matching, as returned by `PathFinder::matching()`.
+pub struct Matching<'a, P: ?Sized> {
+    finder: &'a P,
+    key_matching: KeyMatching,
+}
+
+impl<'a, P: ?Sized> Matching<'a, P> {
+    pub(crate) fn new(finder: &'a P, key_matching: KeyMatching) -> Self {
+        Matching {
+            finder,
+            key_matching,
+        }
+    }
+}
+
+impl<P: PathFinder + ?Sized> PathFinder for Matching<'_, P> {
+    fn data(&self) -> &Yaml {
+        self.finder.data()
+    }
+
+    fn ancestors(&self) -> Vec<&Yaml> {
+        self.finder.ancestors()
+    }
+
+    fn key_matching(&self) -> KeyMatching {
+        self.key_matching
+    }
+
+    fn default_locale(&self) -> &str {
+        self.finder.default_locale()
+    }
+
+    fn date_formats(&self) -> &[DateFormat] {
+        self.finder.date_formats()
+    }
+}
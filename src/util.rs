@@ -15,12 +15,13 @@
 //! ```
 //!
 //! you can access "ruby" like this: `get_string("programmer/languages/1")`.
-//! Leading `/` will not be regarded.
+//! A leading `/` anchors the path at the document root, which only matters for scoped finders.
 
 #![allow(dead_code)]
 
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
 
 #[cfg(feature = "date_parsing")]
 use chrono::prelude::*;
@@ -30,18 +31,176 @@ use yaml_rust::YamlLoader;
 
 /// Wrapper that opens and parses a `.yml` file.
 pub fn open(path: &Path) -> Result<Yaml, Box<dyn std::error::Error>> {
-    let file_content = fs::read_to_string(&path)?;
+    let file_content = fs::read_to_string(path)?;
     parse(&file_content)
 }
 
 /// Ruby like API to yaml-rust.
 pub fn parse(file_content: &str) -> Result<Yaml, Box<dyn std::error::Error>> {
-    Ok(YamlLoader::load_from_str(&file_content)?
-        .get(0)
+    Ok(YamlLoader::load_from_str(file_content)?
+        .first()
         .map(ToOwned::to_owned)
         .unwrap_or_else(|| Yaml::from_str("[]")))
 }
 
+/// Interprets `"1h30m"`, `"250ms"` or `"2 days 4h"` as duration, a plain number as seconds.
+pub fn parse_duration(duration: &str) -> Option<Duration> {
+    let mut rest = duration.trim();
+    if let Ok(seconds) = rest.parse::<f64>() {
+        return Duration::try_from_secs_f64(seconds).ok();
+    }
+    if rest.is_empty() {
+        return None;
+    }
+    let mut total = Duration::ZERO;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount: u64 = rest[..digits].parse().ok()?;
+        rest = rest[digits..].trim_start();
+        let letters = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let nanos: u64 = match &rest[..letters] {
+            "ns" | "nsec" => 1,
+            "us" | "µs" | "usec" => 1_000,
+            "ms" | "msec" => 1_000_000,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1_000_000_000,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60 * 1_000_000_000,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3_600 * 1_000_000_000,
+            "d" | "day" | "days" => 86_400 * 1_000_000_000,
+            "w" | "week" | "weeks" => 604_800 * 1_000_000_000,
+            _ => return None,
+        };
+        rest = rest[letters..].trim_start();
+        total = total.checked_add(Duration::from_nanos(amount.checked_mul(nanos)?))?;
+    }
+    Some(total)
+}
+
+/// Checks the rough shape of an address like `jane.doe@example.com`.
+///
+/// Dot-atom local part of at most 64 characters and a domain of hostname labels,
+/// quoted local parts and address literals are not accepted.
+pub fn is_email(address: &str) -> bool {
+    let (local, domain) = match address.rsplit_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    let atext = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c);
+    let label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    local.len() <= 64
+        && local
+            .split('.')
+            .all(|atom| !atom.is_empty() && atom.chars().all(atext))
+        && domain.len() <= 255
+        && domain.contains('.')
+        && domain.split('.').all(label)
+}
+
+/// Interprets `"#ff8800"`, `"#f80"` or `"rgb(255, 136, 0)"` as red, green and blue.
+pub fn parse_color(color: &str) -> Option<(u8, u8, u8)> {
+    let color = color.trim();
+    if let Some(hex) = color.strip_prefix('#') {
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+        return match hex.len() {
+            3 => {
+                let short = |i: usize| channel(&hex[i..=i]).map(|c| c * 0x11);
+                Some((short(0)?, short(1)?, short(2)?))
+            }
+            6 => Some((
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            _ => None,
+        };
+    }
+    let channels: Vec<u8> = color
+        .strip_prefix("rgb(")?
+        .strip_suffix(')')?
+        .split(',')
+        .map(|channel| channel.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match channels[..] {
+        [r, g, b] => Some((r, g, b)),
+        _ => None,
+    }
+}
+
+/// Decodes standard base64, padding is optional and whitespace is ignored.
+pub fn decode_base64(encoded: &str) -> Option<Vec<u8>> {
+    let symbols: Vec<u8> = encoded
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    let unpadded = symbols
+        .strip_suffix(b"==")
+        .or_else(|| symbols.strip_suffix(b"="))
+        .unwrap_or(&symbols);
+    if (unpadded.len() < symbols.len() && symbols.len() % 4 != 0) || unpadded.len() % 4 == 1 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(unpadded.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &symbol in unpadded {
+        let value = match symbol {
+            b'A'..=b'Z' => symbol - b'A',
+            b'a'..=b'z' => symbol - b'a' + 26,
+            b'0'..=b'9' => symbol - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = buffer << 6 | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    // leftover bits have to be zero, otherwise the input was cut off
+    if buffer != 0 {
+        return None;
+    }
+    Some(bytes)
+}
+
+/// Decodes hex like `"0xdeadbeef"` or `"de:ad:be:ef"`, ignoring `:`, `-`, `_` and whitespace.
+pub fn decode_hex(encoded: &str) -> Option<Vec<u8>> {
+    let encoded = encoded.trim();
+    let encoded = encoded
+        .strip_prefix("0x")
+        .or_else(|| encoded.strip_prefix("0X"))
+        .unwrap_or(encoded);
+    let digits = encoded
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | '_') && !c.is_whitespace())
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    Some(
+        digits
+            .chunks(2)
+            .map(|pair| pair[0] << 4 | pair[1])
+            .collect(),
+    )
+}
+
 /// Interprets `"25.12.2016"` as date.
 #[cfg(feature = "date_parsing")]
 pub fn parse_dmy_date(date_str: &str) -> Option<Date<Utc>> {
@@ -58,6 +217,84 @@ pub fn parse_dmy_date(date_str: &str) -> Option<Date<Utc>> {
     }
 }
 
+/// Interprets a unix epoch in seconds, or in milliseconds if it has more than eleven digits.
+#[cfg(feature = "date_parsing")]
+pub fn parse_timestamp(epoch: i64) -> Option<DateTime<Utc>> {
+    if epoch.unsigned_abs() < 100_000_000_000 {
+        Utc.timestamp_opt(epoch, 0).single()
+    } else {
+        Utc.timestamp_millis_opt(epoch).single()
+    }
+}
+
+/// Interprets `"2019-11-07T13:45:00+01:00"` as datetime.
+///
+/// Also takes YAML's looser timestamps like `2001-12-14 21:59:43.10 -5`,
+/// without an offset they are UTC, a plain date like `2002-12-14` is midnight UTC.
+#[cfg(feature = "date_parsing")]
+pub fn parse_datetime(datetime: &str) -> Option<DateTime<FixedOffset>> {
+    let datetime = datetime.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(datetime) {
+        return Some(parsed);
+    }
+    let split = datetime
+        .find(['t', 'T', ' ', '\t'])
+        .unwrap_or(datetime.len());
+    let date = NaiveDate::parse_from_str(&datetime[..split], "%Y-%m-%d").ok()?;
+    // the separator is a single ascii character
+    let rest = datetime[split..].get(1..).unwrap_or("").trim_start();
+    if rest.is_empty() {
+        return Some(FixedOffset::east_opt(0)?.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?));
+    }
+    let zone = rest.find(['Z', 'z', '+', '-']).unwrap_or(rest.len());
+    let time = NaiveTime::parse_from_str(rest[..zone].trim_end(), "%H:%M:%S%.f").ok()?;
+    parse_offset(&rest[zone..])?
+        .from_local_datetime(&date.and_time(time))
+        .single()
+}
+
+/// Interprets `"Z"`, `"+01:00"`, `"+0100"` or `"-5"` as offset from UTC.
+#[cfg(feature = "date_parsing")]
+fn parse_offset(zone: &str) -> Option<FixedOffset> {
+    if zone.is_empty() || zone.eq_ignore_ascii_case("z") {
+        return FixedOffset::east_opt(0);
+    }
+    let sign = match zone.as_bytes()[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = &zone[1..];
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit() || b == b':') {
+        return None;
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some(parts) => parts,
+        None if digits.len() > 2 => digits.split_at(2),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Interprets `"25.12.2016"` as date without timezone.
+#[cfg(feature = "date_parsing")]
+pub fn parse_dmy_naive_date(date_str: &str) -> Option<NaiveDate> {
+    let date = date_str
+        .split('.')
+        .map(|f| f.parse().unwrap_or(0))
+        .collect::<Vec<i32>>();
+    if date.len() == 3 && date[0] > 0 && date[1] > 0 && date[2] > 1900 {
+        NaiveDate::from_ymd_opt(date[2], date[1] as u32, date[0] as u32)
+    } else {
+        None
+    }
+}
+
 /// Interprets `"24-25.12.2016"` as date.
 ///
 /// Takes care of the old, deprecated, stupid, `dd-dd.mm.yyyy` format, what was I thinking?
@@ -19,7 +19,7 @@ where
     }
 }
 
-pub(crate) type DynRule = dyn (Fn(&Yaml) -> Box<dyn Invalidatable>);
+pub(crate) type DynRule = dyn Fn(&Yaml) -> Box<dyn Invalidatable>;
 
 fn box_rule(rule: impl Rule) -> Box<DynRule> {
     Box::new(move |cx| Box::new(rule.call(cx)))